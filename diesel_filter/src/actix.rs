@@ -0,0 +1,161 @@
+use crate::{FilterParseError, FilterRuleError, FromQueryStr, query::parse_list_query};
+use actix_web::{
+    Error, FromRequest, HttpRequest, HttpResponse, ResponseError, dev::Payload, http::StatusCode,
+};
+use diesel_pagination::PaginationParams;
+use serde::de::DeserializeOwned;
+use std::{
+    fmt,
+    future::{Ready, ready},
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+type ErrorHandler = dyn Fn(FilterParseError, &HttpRequest) -> Error + Send + Sync;
+
+/// Configures the `Filters` and `ListQuery` extractors, to be registered with `App::app_data`.
+///
+/// ```ignore
+/// App::new().app_data(FiltersConfig::default().error_handler(|err, _req| {
+///     MyApiError::from(err).into()
+/// }))
+/// ```
+#[derive(Clone, Default)]
+pub struct FiltersConfig {
+    err_handler: Option<Arc<ErrorHandler>>,
+}
+
+impl FiltersConfig {
+    /// Sets a custom error handler, the default one responds with a `400 Bad Request`
+    /// and a JSON body listing every invalid parameter.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(FilterParseError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    fn from_req(req: &HttpRequest) -> &Self {
+        static DEFAULT: FiltersConfig = FiltersConfig { err_handler: None };
+        req.app_data::<Self>().unwrap_or(&DEFAULT)
+    }
+
+    fn error(req: &HttpRequest, err: FilterParseError) -> Error {
+        match &Self::from_req(req).err_handler {
+            Some(err_handler) => err_handler(err, req),
+            None => err.into(),
+        }
+    }
+}
+
+impl fmt::Debug for FiltersConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FiltersConfig")
+            .field("err_handler", &self.err_handler.is_some())
+            .finish()
+    }
+}
+
+/// Extracts the generated filters from the query string, they must be derived
+/// with `#[filter_struct(query_string)]`.
+///
+/// ```ignore
+/// async fn list(Filters(filters): Filters<ProjectFilters>) -> impl Responder { ... }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Filters<T>(pub T);
+
+impl<T> Filters<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Filters<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Filters<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> FromRequest for Filters<T>
+where
+    T: FromQueryStr,
+{
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            T::from_query_str(req.query_string())
+                .map(Self)
+                .map_err(|err| FiltersConfig::error(req, err)),
+        )
+    }
+}
+
+/// Extracts the generated filters and the pagination parameters from the same query string,
+/// the filters must be derived with `#[filter_struct(query_string)]`.
+///
+/// ```ignore
+/// async fn list(ListQuery { filters, pagination }: ListQuery<ProjectFilters>) -> impl Responder { ... }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ListQuery<F, P = PaginationParams> {
+    pub filters: F,
+    pub pagination: P,
+}
+
+impl<F, P> ListQuery<F, P> {
+    pub fn into_parts(self) -> (F, P) {
+        (self.filters, self.pagination)
+    }
+}
+
+impl<F, P> FromRequest for ListQuery<F, P>
+where
+    F: FromQueryStr,
+    P: DeserializeOwned,
+{
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            parse_list_query(req.query_string())
+                .map(|(filters, pagination)| Self {
+                    filters,
+                    pagination,
+                })
+                .map_err(|err| FiltersConfig::error(req, err)),
+        )
+    }
+}
+
+impl ResponseError for FilterParseError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self)
+    }
+}
+
+impl ResponseError for FilterRuleError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self)
+    }
+}
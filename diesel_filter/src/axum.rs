@@ -0,0 +1,115 @@
+use crate::{FilterParseError, FilterRuleError, FromQueryStr, query::parse_list_query};
+use ::axum::{
+    Json,
+    extract::FromRequestParts,
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use diesel_pagination::PaginationParams;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// Extracts the generated filters and the pagination parameters from the same query string,
+/// the filters must be derived with `#[filter_struct(query_string)]`.
+///
+/// ```ignore
+/// async fn list(ListQuery { filters, pagination, .. }: ListQuery<ProjectFilters>) { ... }
+/// ```
+///
+/// Invalid parameters are rejected with a `400 Bad Request` and a JSON body listing
+/// every invalid field, a custom rejection implementing `From<FilterParseError>`
+/// can be used instead through the `R` parameter.
+#[derive(Debug, Clone)]
+pub struct ListQuery<F, P = PaginationParams, R = FilterParseError> {
+    pub filters: F,
+    pub pagination: P,
+    _rejection: PhantomData<R>,
+}
+
+impl<F, P, R> ListQuery<F, P, R> {
+    pub fn into_parts(self) -> (F, P) {
+        (self.filters, self.pagination)
+    }
+}
+
+impl<F, P, R, S> FromRequestParts<S> for ListQuery<F, P, R>
+where
+    F: FromQueryStr,
+    P: DeserializeOwned,
+    R: From<FilterParseError> + IntoResponse,
+    S: Send + Sync,
+{
+    type Rejection = R;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let (filters, pagination) = parse_list_query(query)?;
+
+        Ok(Self {
+            filters,
+            pagination,
+            _rejection: PhantomData,
+        })
+    }
+}
+
+impl IntoResponse for FilterParseError {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for FilterRuleError {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, Json(self)).into_response()
+    }
+}
+
+#[cfg(feature = "aide")]
+mod with_aide {
+    use super::ListQuery;
+    use crate::FilterParseError;
+    use ::axum::{Json, extract::Query};
+    use aide::{
+        OperationInput, OperationOutput,
+        generate::GenContext,
+        openapi::{Operation, Response},
+    };
+    use schemars::JsonSchema;
+
+    impl<F, P, R> OperationInput for ListQuery<F, P, R>
+    where
+        F: JsonSchema,
+        P: JsonSchema,
+        R: OperationOutput,
+    {
+        fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+            Query::<F>::operation_input(ctx, operation);
+            Query::<P>::operation_input(ctx, operation);
+        }
+
+        fn inferred_early_responses(
+            ctx: &mut GenContext,
+            operation: &mut Operation,
+        ) -> Vec<(Option<u16>, Response)> {
+            R::inferred_responses(ctx, operation)
+        }
+    }
+
+    impl OperationOutput for FilterParseError {
+        type Inner = Self;
+
+        fn operation_response(ctx: &mut GenContext, operation: &mut Operation) -> Option<Response> {
+            Json::<Self>::operation_response(ctx, operation)
+        }
+
+        fn inferred_responses(
+            ctx: &mut GenContext,
+            operation: &mut Operation,
+        ) -> Vec<(Option<u16>, Response)> {
+            Self::operation_response(ctx, operation)
+                .map(|response| vec![(Some(400), response)])
+                .unwrap_or_default()
+        }
+    }
+}
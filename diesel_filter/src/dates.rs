@@ -0,0 +1,484 @@
+//! Calendar-aware filters generated by `#[filter(date)]`: `{field}_on`, `{field}_before`,
+//! `{field}_after` and `{field}_within`, resolved in the time zone of the filter.
+
+use chrono::{
+    DateTime, Datelike, Days, Duration, FixedOffset, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc,
+};
+use std::{fmt, str::FromStr};
+
+const LOCAL_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+/// The types of the columns filtered by `#[filter(date)]`.
+///
+/// Ranges are resolved as date-times in the time zone of the filter, then converted to the
+/// type of the column. Dates cover the days the range overlaps.
+pub trait DateBound: Sized {
+    /// The inclusive lower bound of a range starting at `moment`.
+    fn lower<Tz: TimeZone>(moment: DateTime<Tz>) -> Self;
+
+    /// The exclusive upper bound of a range ending at `moment`.
+    fn upper<Tz: TimeZone>(moment: DateTime<Tz>) -> Self;
+}
+
+impl DateBound for DateTime<Utc> {
+    fn lower<Tz: TimeZone>(moment: DateTime<Tz>) -> Self {
+        moment.to_utc()
+    }
+
+    fn upper<Tz: TimeZone>(moment: DateTime<Tz>) -> Self {
+        moment.to_utc()
+    }
+}
+
+/// Naive date-times are read as UTC.
+impl DateBound for NaiveDateTime {
+    fn lower<Tz: TimeZone>(moment: DateTime<Tz>) -> Self {
+        moment.naive_utc()
+    }
+
+    fn upper<Tz: TimeZone>(moment: DateTime<Tz>) -> Self {
+        moment.naive_utc()
+    }
+}
+
+impl DateBound for NaiveDate {
+    fn lower<Tz: TimeZone>(moment: DateTime<Tz>) -> Self {
+        moment.date_naive()
+    }
+
+    fn upper<Tz: TimeZone>(moment: DateTime<Tz>) -> Self {
+        let local = moment.naive_local();
+        if local.time() == NaiveTime::MIN {
+            local.date()
+        } else {
+            next_day(local.date())
+        }
+    }
+}
+
+/// The earliest moment of a local date-time, or the first valid one after it
+/// when it is skipped by a change of offset.
+///
+/// Gaps are looked up to `MAX_GAP` ahead, after that (or at the end of the calendar)
+/// the local date-time is read as UTC.
+fn local<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> DateTime<Tz> {
+    const STEP: Duration = Duration::minutes(15);
+    const MAX_GAP: Duration = Duration::hours(6);
+
+    let mut moment = local;
+    while moment - local <= MAX_GAP {
+        if let Some(resolved) = tz.from_local_datetime(&moment).earliest() {
+            return resolved;
+        }
+        match moment.checked_add_signed(STEP) {
+            Some(next) => moment = next,
+            None => break,
+        }
+    }
+    tz.from_utc_datetime(&local)
+}
+
+fn start_of_day<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> DateTime<Tz> {
+    local(tz, date.and_time(NaiveTime::MIN))
+}
+
+fn next_day(date: NaiveDate) -> NaiveDate {
+    date.succ_opt().unwrap_or(date)
+}
+
+fn today<Tz: TimeZone>(tz: &Tz, now: DateTime<Utc>) -> NaiveDate {
+    now.with_timezone(tz).date_naive()
+}
+
+/// The whole days from `start` to `end`, excluded.
+fn days<T: DateBound, Tz: TimeZone>(tz: &Tz, start: NaiveDate, end: NaiveDate) -> (T, T) {
+    (
+        T::lower(start_of_day(tz, start)),
+        T::upper(start_of_day(tz, end)),
+    )
+}
+
+/// An amount of time like `7d` or `2h`.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let amount: i64 = value[..digits]
+        .parse()
+        .map_err(|_| format!("invalid duration `{value}`"))?;
+    let duration = match &value[digits..] {
+        "s" => Duration::try_seconds(amount),
+        "m" | "min" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        unit => return Err(format!("unknown unit `{unit}`, expected s, m, h, d or w")),
+    };
+    duration.ok_or_else(|| format!("invalid duration `{value}`"))
+}
+
+/// Offsets like `-7d` or `-1w+2h`. A `+` left unencoded in a query string is read as a space.
+pub(crate) fn parse_offsets(mut offsets: &str) -> Result<Duration, String> {
+    let mut total = Duration::zero();
+    while !offsets.is_empty() {
+        let negative = match offsets.as_bytes()[0] {
+            b'+' | b' ' => false,
+            b'-' => true,
+            _ => return Err(format!("invalid offset `{offsets}`")),
+        };
+        let rest = &offsets[1..];
+        let end = rest.find(['+', '-', ' ']).unwrap_or(rest.len());
+        let duration = parse_duration(&rest[..end])?;
+        let moved = if negative {
+            total.checked_sub(&duration)
+        } else {
+            total.checked_add(&duration)
+        };
+        total = moved.ok_or_else(|| format!("invalid offset `{offsets}`"))?;
+        offsets = &rest[end..];
+    }
+    Ok(total)
+}
+
+/// Writes a duration with the largest unit it is a whole number of.
+fn write_duration(f: &mut fmt::Formatter<'_>, duration: Duration) -> fmt::Result {
+    let seconds = duration.num_seconds();
+    for (unit, length) in [("w", 604_800), ("d", 86_400), ("h", 3_600), ("m", 60)] {
+        if seconds % length == 0 {
+            return write!(f, "{}{unit}", seconds / length);
+        }
+    }
+    write!(f, "{seconds}s")
+}
+
+fn write_offset(f: &mut fmt::Formatter<'_>, offset: Duration) -> fmt::Result {
+    if offset.is_zero() {
+        Ok(())
+    } else if offset < Duration::zero() {
+        f.write_str("-")?;
+        write_duration(f, -offset)
+    } else {
+        f.write_str("+")?;
+        write_duration(f, offset)
+    }
+}
+
+/// The value of `{field}_on`: a day like `2024-01-31`, or relative to the current day
+/// like `today`, `yesterday` or `today-7d`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Day {
+    Date(NaiveDate),
+    /// Days from the current day in the time zone of the filter.
+    Today(i64),
+}
+
+impl Day {
+    pub fn date<Tz: TimeZone>(&self, tz: &Tz) -> NaiveDate {
+        self.date_at(tz, Utc::now())
+    }
+
+    /// Same as `date`, with the current time given as `now`.
+    pub fn date_at<Tz: TimeZone>(&self, tz: &Tz, now: DateTime<Utc>) -> NaiveDate {
+        match *self {
+            Self::Date(date) => date,
+            Self::Today(days) => {
+                let today = today(tz, now);
+                let moved = if days < 0 {
+                    today.checked_sub_days(Days::new(days.unsigned_abs()))
+                } else {
+                    today.checked_add_days(Days::new(days.unsigned_abs()))
+                };
+                moved.unwrap_or(today)
+            }
+        }
+    }
+
+    /// The bounds of the whole day.
+    pub fn bounds<T: DateBound, Tz: TimeZone>(&self, tz: &Tz) -> (T, T) {
+        self.bounds_at(tz, Utc::now())
+    }
+
+    /// Same as `bounds`, with the current time given as `now`.
+    pub fn bounds_at<T: DateBound, Tz: TimeZone>(&self, tz: &Tz, now: DateTime<Utc>) -> (T, T) {
+        let date = self.date_at(tz, now);
+        days(tz, date, next_day(date))
+    }
+}
+
+impl FromStr for Day {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "yesterday" => return Ok(Self::Today(-1)),
+            "tomorrow" => return Ok(Self::Today(1)),
+            _ => {}
+        }
+        if let Some(offsets) = value.strip_prefix("today") {
+            let offset = parse_offsets(offsets)?;
+            if offset != Duration::days(offset.num_days()) {
+                return Err("expected an offset in days, like today-7d".to_owned());
+            }
+            return Ok(Self::Today(offset.num_days()));
+        }
+        value
+            .parse()
+            .map(Self::Date)
+            .map_err(|_| "expected a date like 2024-01-31, today or today-7d".to_owned())
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Date(date) => date.fmt(f),
+            Self::Today(days) => {
+                f.write_str("today")?;
+                write_offset(f, Duration::days(days))
+            }
+        }
+    }
+}
+
+/// The value of `{field}_before` and `{field}_after`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Moment {
+    /// The start of a day, e.g. `2024-01-31` or `today-7d`.
+    Day(Day),
+    /// A date and time in the time zone of the filter, e.g. `2024-01-31T12:00`.
+    Local(NaiveDateTime),
+    /// A date and time with an offset, e.g. `2024-01-31T12:00:00Z`.
+    Instant(DateTime<FixedOffset>),
+    /// Relative to the current time, e.g. `now` or `now-2h`.
+    Now(Duration),
+}
+
+impl Moment {
+    pub fn resolve<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Tz> {
+        self.resolve_at(tz, Utc::now())
+    }
+
+    /// Same as `resolve`, with the current time given as `now`.
+    pub fn resolve_at<Tz: TimeZone>(&self, tz: &Tz, now: DateTime<Utc>) -> DateTime<Tz> {
+        match self {
+            Self::Day(day) => start_of_day(tz, day.date_at(tz, now)),
+            Self::Local(moment) => local(tz, *moment),
+            Self::Instant(moment) => moment.with_timezone(tz),
+            Self::Now(offset) => now
+                .checked_add_signed(*offset)
+                .unwrap_or(now)
+                .with_timezone(tz),
+        }
+    }
+
+    /// The inclusive lower bound of `{field}_after`.
+    pub fn lower<T: DateBound, Tz: TimeZone>(&self, tz: &Tz) -> T {
+        self.lower_at(tz, Utc::now())
+    }
+
+    /// Same as `lower`, with the current time given as `now`.
+    pub fn lower_at<T: DateBound, Tz: TimeZone>(&self, tz: &Tz, now: DateTime<Utc>) -> T {
+        T::lower(self.resolve_at(tz, now))
+    }
+
+    /// The exclusive upper bound of `{field}_before`.
+    pub fn upper<T: DateBound, Tz: TimeZone>(&self, tz: &Tz) -> T {
+        self.upper_at(tz, Utc::now())
+    }
+
+    /// Same as `upper`, with the current time given as `now`.
+    pub fn upper_at<T: DateBound, Tz: TimeZone>(&self, tz: &Tz, now: DateTime<Utc>) -> T {
+        T::upper(self.resolve_at(tz, now))
+    }
+}
+
+impl FromStr for Moment {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(offsets) = value.strip_prefix("now") {
+            return parse_offsets(offsets).map(Self::Now);
+        }
+        if let Ok(day) = value.parse() {
+            return Ok(Self::Day(day));
+        }
+        if let Ok(moment) = DateTime::parse_from_rfc3339(value) {
+            return Ok(Self::Instant(moment));
+        }
+        LOCAL_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .map(Self::Local)
+            .ok_or_else(|| {
+                "expected a date like 2024-01-31, a date-time like 2024-01-31T12:00, today-7d or now-2h"
+                    .to_owned()
+            })
+    }
+}
+
+impl fmt::Display for Moment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Day(day) => day.fmt(f),
+            Self::Local(moment) => moment.format("%Y-%m-%dT%H:%M:%S%.f").fmt(f),
+            Self::Instant(moment) => f.write_str(&moment.to_rfc3339()),
+            Self::Now(offset) => {
+                f.write_str("now")?;
+                write_offset(f, *offset)
+            }
+        }
+    }
+}
+
+/// The value of `{field}_within`: the last hours, days or weeks like `24h` or `7d`,
+/// or a calendar period like `this_month`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    /// Until now.
+    Last(Duration),
+    Today,
+    Yesterday,
+    /// Weeks start on Monday.
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+    LastMonth,
+    ThisYear,
+    LastYear,
+}
+
+impl Period {
+    /// The bounds of the period, whole days and months included.
+    pub fn bounds<T: DateBound, Tz: TimeZone>(&self, tz: &Tz) -> (T, T) {
+        self.bounds_at(tz, Utc::now())
+    }
+
+    /// Same as `bounds`, with the current time given as `now`.
+    pub fn bounds_at<T: DateBound, Tz: TimeZone>(&self, tz: &Tz, now: DateTime<Utc>) -> (T, T) {
+        let today = today(tz, now);
+        let week = today.week(chrono::Weekday::Mon).first_day();
+        let month = today.with_day(1).unwrap_or(today);
+        let year = today.with_ordinal(1).unwrap_or(today);
+        let before = |date: NaiveDate, months: u32| {
+            date.checked_sub_months(Months::new(months)).unwrap_or(date)
+        };
+        let after = |date: NaiveDate, months: u32| {
+            date.checked_add_months(Months::new(months)).unwrap_or(date)
+        };
+        match *self {
+            Self::Last(duration) => {
+                let start = now.checked_sub_signed(duration).unwrap_or(now);
+                (
+                    T::lower(start.with_timezone(tz)),
+                    T::upper(now.with_timezone(tz)),
+                )
+            }
+            Self::Today => days(tz, today, next_day(today)),
+            Self::Yesterday => days(tz, today.pred_opt().unwrap_or(today), today),
+            Self::ThisWeek => days(tz, week, week + Days::new(7)),
+            Self::LastWeek => days(tz, week - Days::new(7), week),
+            Self::ThisMonth => days(tz, month, after(month, 1)),
+            Self::LastMonth => days(tz, before(month, 1), month),
+            Self::ThisYear => days(tz, year, after(year, 12)),
+            Self::LastYear => days(tz, before(year, 12), year),
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "today" => Self::Today,
+            "yesterday" => Self::Yesterday,
+            "this_week" => Self::ThisWeek,
+            "last_week" => Self::LastWeek,
+            "this_month" => Self::ThisMonth,
+            "last_month" => Self::LastMonth,
+            "this_year" => Self::ThisYear,
+            "last_year" => Self::LastYear,
+            _ => {
+                let duration = parse_duration(value).map_err(|_| {
+                    "expected a duration like 7d or 24h, or a period like today, this_week, \
+                     last_month or this_year"
+                        .to_owned()
+                })?;
+                if duration < Duration::zero() {
+                    return Err(format!("invalid duration `{value}`"));
+                }
+                Self::Last(duration)
+            }
+        })
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Last(duration) => return write_duration(f, *duration),
+            Self::Today => "today",
+            Self::Yesterday => "yesterday",
+            Self::ThisWeek => "this_week",
+            Self::LastWeek => "last_week",
+            Self::ThisMonth => "this_month",
+            Self::LastMonth => "last_month",
+            Self::ThisYear => "this_year",
+            Self::LastYear => "last_year",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(feature = "rocket")]
+mod rocket {
+    use super::{Day, Moment, Period};
+    use ::rocket::form::{self, FromFormField, ValueField};
+
+    macro_rules! from_form_field {
+        ($($ty:ty),*) => {$(
+            impl<'v> FromFormField<'v> for $ty {
+                fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+                    field
+                        .value
+                        .parse()
+                        .map_err(|err: String| form::Error::validation(err).into())
+                }
+            }
+        )*};
+    }
+
+    from_form_field!(Day, Moment, Period);
+}
+
+#[cfg(feature = "async-graphql")]
+mod graphql {
+    use super::{Day, Moment, Period};
+    use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+    macro_rules! scalar {
+        ($($ty:ident),*) => {$(
+            #[Scalar]
+            impl ScalarType for $ty {
+                fn parse(value: Value) -> InputValueResult<Self> {
+                    match &value {
+                        Value::String(text) => text.parse().map_err(InputValueError::custom),
+                        _ => Err(InputValueError::expected_type(value)),
+                    }
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::String(self.to_string())
+                }
+            }
+        )*};
+    }
+
+    scalar!(Day, Moment, Period);
+}
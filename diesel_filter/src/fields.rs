@@ -0,0 +1,57 @@
+/// Describes a filter of a generated struct, see `[YourStruct]Filters::FIELDS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FilterField {
+    /// Name of the query parameter.
+    pub name: &'static str,
+    /// Rust type of the values, without `Option` or `Vec`.
+    pub value_type: &'static str,
+    /// How the values are compared to the column.
+    pub operators: &'static [FilterOperator],
+    /// Whether the column is nullable, i.e. the field of the model is an `Option`.
+    pub nullable: bool,
+    /// Maximum number of values of a `multiple` filter.
+    pub max: Option<usize>,
+    /// Maximum number of characters of each value.
+    pub max_len: Option<usize>,
+    /// Doc comment of the field of the model.
+    pub description: Option<&'static str>,
+}
+
+/// The options given to `#[filter(...)]`, `Eq` when the values are compared as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum FilterOperator {
+    Eq,
+    Substring,
+    Insensitive,
+    Multiple,
+    /// `{field}_on` of `#[filter(date)]`, the whole day.
+    On,
+    /// `{field}_before` of `#[filter(date)]`, excluded.
+    Before,
+    /// `{field}_after` of `#[filter(date)]`, included.
+    After,
+    /// `{field}_within` of `#[filter(date)]`, a duration until now or a calendar period.
+    Within,
+    /// `{field}_since` of `#[filter(lenient(range))]`, included.
+    Since,
+    /// `{field}_until` of `#[filter(lenient(range))]`, excluded, a date-only value of a
+    /// date-time including the day.
+    Until,
+    /// `{field}_contains` of `#[filter(array(contains))]`, all the values, or of
+    /// `#[filter(jsonb)]`, a JSON value, `@>`.
+    Contains,
+    /// `{field}_overlaps` of `#[filter(array(overlaps))]`, any of the values, `&&`.
+    Overlaps,
+    /// `{field}_contained_by` of `#[filter(array(contained_by))]`, only the values, `<@`.
+    ContainedBy,
+    /// `{field}_has_key` of `#[filter(jsonb)]`, a top-level key, `?`, or any of the keys
+    /// with `multiple`, `?|`.
+    HasKey,
+}
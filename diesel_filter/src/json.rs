@@ -0,0 +1,132 @@
+//! Values and in-memory matching of the `#[filter(jsonb)]` filters.
+
+use serde_json::Value;
+use std::{fmt, ops::Deref, str::FromStr};
+
+/// A JSON document given to `{field}_contains`, e.g. `{"address":{"city":"Paris"}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonValue(pub Value);
+
+impl Deref for JsonValue {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromStr for JsonValue {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(value)
+            .map(Self)
+            .map_err(|err| err.to_string())
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The value at `path` as text, as `#>>` does: strings without their quotes, `None`
+/// when the path is missing or the value is `null`.
+///
+/// Numbers, booleans, arrays and objects are compact JSON, while Postgres puts spaces
+/// in arrays and objects.
+pub fn path_text(json: &Value, path: &[&str]) -> Option<String> {
+    let mut value = json;
+    for key in path {
+        value = match value {
+            Value::Object(map) => map.get(*key)?,
+            // Negative indices count from the end of the array
+            Value::Array(values) => {
+                let index = key.parse::<isize>().ok()?;
+                let index = if index < 0 {
+                    values.len().checked_sub(index.unsigned_abs())?
+                } else {
+                    index.unsigned_abs()
+                };
+                values.get(index)?
+            }
+            _ => return None,
+        };
+    }
+
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        value => Some(value.to_string()),
+    }
+}
+
+/// Whether `json` contains `other`, as `@>` does: the keys of objects and the values of
+/// arrays are contained recursively, and a top-level array contains its primitive values.
+pub fn contains(json: &Value, other: &Value) -> bool {
+    match (json, other) {
+        // Only at the top level, `{"tags":["bread"]}` doesn't contain `{"tags":"bread"}`
+        (Value::Array(json), other) if !other.is_object() && !other.is_array() => {
+            json.iter().any(|value| value == other)
+        }
+        (json, other) => contains_nested(json, other),
+    }
+}
+
+fn contains_nested(json: &Value, other: &Value) -> bool {
+    match (json, other) {
+        (Value::Object(json), Value::Object(other)) => other.iter().all(|(key, other)| {
+            json.get(key)
+                .is_some_and(|value| contains_nested(value, other))
+        }),
+        (Value::Array(json), Value::Array(other)) => other
+            .iter()
+            .all(|other| json.iter().any(|value| contains_nested(value, other))),
+        (json, other) => json == other,
+    }
+}
+
+/// Whether `json` has the top-level `key`, as `?` does: a key of an object, or a string
+/// of an array.
+pub fn has_key(json: &Value, key: &str) -> bool {
+    match json {
+        Value::Object(map) => map.contains_key(key),
+        Value::Array(values) => values.iter().any(|value| value.as_str() == Some(key)),
+        Value::String(text) => text == key,
+        _ => false,
+    }
+}
+
+#[cfg(feature = "rocket")]
+mod rocket {
+    use super::JsonValue;
+    use ::rocket::form::{self, FromFormField, ValueField};
+
+    impl<'v> FromFormField<'v> for JsonValue {
+        fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+            field
+                .value
+                .parse()
+                .map_err(|err: String| form::Error::validation(err).into())
+        }
+    }
+}
+
+#[cfg(feature = "async-graphql")]
+mod graphql {
+    use super::JsonValue;
+    use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+    /// Any GraphQL value, strings being JSON strings rather than JSON texts.
+    #[Scalar]
+    impl ScalarType for JsonValue {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            value.into_json().map(Self).map_err(InputValueError::custom)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::from_json(self.0.clone()).unwrap_or(Value::Null)
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Lenient parsing of the filter values with `#[filter(lenient)]`, accepting the formats
+//! of HTML forms and hand-written query strings.
+
+/// Parses the value of a `#[filter(lenient)]` filter.
+///
+/// Booleans accept `true/false`, `1/0`, `yes/no` and `on/off`. With the `chrono` feature,
+/// dates and date-times also accept relative expressions like `now-7d` or `today`, and
+/// date-times accept date-only values, widened to the boundaries of the day.
+pub trait LenientFromStr: Sized {
+    /// Parses a value, or the inclusive lower bound of `{field}_since`:
+    /// date-only values of date-times are the start of the day.
+    fn from_str_lenient(value: &str) -> Result<Self, String>;
+
+    /// Parses the exclusive upper bound of `{field}_until`:
+    /// date-only values of date-times are the start of the next day.
+    fn from_str_lenient_end(value: &str) -> Result<Self, String> {
+        Self::from_str_lenient(value)
+    }
+}
+
+impl LenientFromStr for bool {
+    fn from_str_lenient(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(true),
+            "false" | "0" | "no" | "off" => Ok(false),
+            _ => Err("expected true/false, 1/0, yes/no or on/off".to_owned()),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use super::LenientFromStr;
+    use crate::dates::parse_offsets;
+    use ::chrono::{DateTime, Days, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+    const DATE_TIME_FORMATS: [&str; 4] = [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+
+    /// `now` or `today`, optionally followed by offsets like `-7d` or `+1h`.
+    fn relative(value: &str, now: DateTime<Utc>) -> Option<Result<DateTime<Utc>, String>> {
+        let (moment, offsets) = if let Some(offsets) = value.strip_prefix("now") {
+            (now, offsets)
+        } else if let Some(offsets) = value.strip_prefix("today") {
+            (now.date_naive().and_time(NaiveTime::MIN).and_utc(), offsets)
+        } else {
+            return None;
+        };
+
+        Some(parse_offsets(offsets).and_then(|offset| {
+            moment
+                .checked_add_signed(offset)
+                .ok_or_else(|| format!("invalid offset `{offsets}`"))
+        }))
+    }
+
+    /// A date-time, or a date-only value as the start of that day or of the next one.
+    fn date_time(value: &str, end: bool) -> Result<DateTime<Utc>, String> {
+        if let Some(moment) = relative(value, Utc::now()) {
+            return moment;
+        }
+        if let Ok(moment) = DateTime::parse_from_rfc3339(value) {
+            return Ok(moment.to_utc());
+        }
+        if let Ok(moment) = value.parse::<DateTime<Utc>>() {
+            return Ok(moment);
+        }
+        for format in DATE_TIME_FORMATS {
+            if let Ok(moment) = NaiveDateTime::parse_from_str(value, format) {
+                return Ok(moment.and_utc());
+            }
+        }
+        match value.parse::<NaiveDate>() {
+            Ok(date) => {
+                let day = if end {
+                    date.checked_add_days(Days::new(1))
+                        .ok_or_else(|| "date out of range".to_owned())?
+                } else {
+                    date
+                };
+                Ok(day.and_time(NaiveTime::MIN).and_utc())
+            }
+            Err(_) => {
+                Err("expected a date-time like 2024-01-31T12:00:00Z, a date or now-7d".to_owned())
+            }
+        }
+    }
+
+    impl LenientFromStr for DateTime<Utc> {
+        fn from_str_lenient(value: &str) -> Result<Self, String> {
+            date_time(value, false)
+        }
+
+        fn from_str_lenient_end(value: &str) -> Result<Self, String> {
+            date_time(value, true)
+        }
+    }
+
+    /// Naive date-times are read as UTC.
+    impl LenientFromStr for NaiveDateTime {
+        fn from_str_lenient(value: &str) -> Result<Self, String> {
+            date_time(value, false).map(|moment| moment.naive_utc())
+        }
+
+        fn from_str_lenient_end(value: &str) -> Result<Self, String> {
+            date_time(value, true).map(|moment| moment.naive_utc())
+        }
+    }
+
+    /// Dates accept relative expressions, `now` being the current day in UTC.
+    impl LenientFromStr for NaiveDate {
+        fn from_str_lenient(value: &str) -> Result<Self, String> {
+            match relative(value, Utc::now()) {
+                Some(moment) => moment.map(|moment| moment.date_naive()),
+                None => value
+                    .parse()
+                    .map_err(|err: ::chrono::ParseError| err.to_string()),
+            }
+        }
+    }
+}
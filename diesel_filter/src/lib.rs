@@ -1,4 +1,29 @@
+#[cfg(feature = "chrono")]
+pub use chrono;
+#[cfg(feature = "serde_json")]
+pub use serde_json;
 #[cfg(any(feature = "actix", feature = "axum"))]
 pub use serde_with;
 
 pub use diesel_filter_query::*;
+
+#[cfg(feature = "chrono")]
+pub mod dates;
+mod fields;
+#[cfg(feature = "serde_json")]
+pub mod json;
+pub mod lenient;
+pub mod matching;
+pub mod options;
+pub mod predicates;
+pub mod query;
+mod rules;
+
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+
+pub use fields::{FilterField, FilterOperator};
+pub use query::{FieldError, FilterParseError, FromQueryStr, QueryStyle};
+pub use rules::FilterRuleError;
@@ -0,0 +1,64 @@
+//! Helpers of the generated `matches` method, evaluating filters in Rust as Postgres does.
+
+enum Token {
+    /// `%`
+    Any,
+    /// `_`
+    One,
+    Char(char),
+}
+
+/// Evaluates `value LIKE pattern`, or `value ILIKE pattern` when `insensitive`.
+///
+/// `%` matches any sequence of characters, `_` any single character and `\` escapes
+/// the next character. A pattern ending with `\`, which Postgres rejects, matches nothing.
+pub fn like(value: &str, pattern: &str, insensitive: bool) -> bool {
+    if insensitive {
+        return like(&value.to_lowercase(), &pattern.to_lowercase(), false);
+    }
+
+    let mut tokens = vec![];
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '%' => Token::Any,
+            '_' => Token::One,
+            '\\' => match chars.next() {
+                Some(c) => Token::Char(c),
+                None => return false,
+            },
+            c => Token::Char(c),
+        });
+    }
+
+    let value: Vec<_> = value.chars().collect();
+    let (mut v, mut t) = (0, 0);
+    // Position of the last `%` and of the value when it was reached, to backtrack to
+    let mut backtrack = None;
+    while v < value.len() {
+        match tokens.get(t) {
+            Some(Token::Any) => {
+                backtrack = Some((t, v));
+                t += 1;
+            }
+            Some(Token::One) => {
+                v += 1;
+                t += 1;
+            }
+            Some(Token::Char(c)) if *c == value[v] => {
+                v += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((any, from)) => {
+                    backtrack = Some((any, from + 1));
+                    t = any + 1;
+                    v = from + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    tokens[t..].iter().all(|token| matches!(token, Token::Any))
+}
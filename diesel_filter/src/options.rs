@@ -0,0 +1,348 @@
+//! Limits, normalization and parsing of the filter values,
+//! e.g. `#[filter(multiple(max = 100), trim, lenient)]`, used by the generated code.
+
+use crate::lenient::LenientFromStr;
+use std::{borrow::Cow, fmt::Display, mem, str::FromStr};
+
+/// The `#[filter(...)]` options applying to the values of a filter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldOptions {
+    /// Maximum number of values of a `multiple` filter.
+    pub max: Option<usize>,
+    /// Maximum number of characters of each value, as written in a query string.
+    pub max_len: Option<usize>,
+    /// Removes the leading and trailing whitespace of the values.
+    pub trim: bool,
+    /// Ignores the empty values, after trimming.
+    pub empty_as_none: bool,
+    /// Lowercases the values.
+    pub lowercase: bool,
+}
+
+impl FieldOptions {
+    /// The value to parse, `None` when it is ignored.
+    pub fn normalize<'a>(&self, value: &'a str) -> Option<Cow<'a, str>> {
+        let value = if self.trim { value.trim() } else { value };
+        if self.empty_as_none && value.is_empty() {
+            return None;
+        }
+        Some(if self.lowercase {
+            Cow::Owned(value.to_lowercase())
+        } else {
+            Cow::Borrowed(value)
+        })
+    }
+
+    /// Normalizes a value parsed by a framework, through its `Display` and `FromStr` implementations.
+    /// The value is kept as is when it can't be parsed back.
+    pub fn normalize_parsed<T: Display + FromStr>(&self, value: T) -> Option<T> {
+        let written = value.to_string();
+        match self.normalize(&written)? {
+            normalized if normalized == written => Some(value),
+            normalized => Some(normalized.parse().unwrap_or(value)),
+        }
+    }
+
+    pub fn normalize_single<T: Display + FromStr>(&self, value: &mut Option<T>) {
+        *value = value.take().and_then(|value| self.normalize_parsed(value));
+    }
+
+    /// Normalizes the values of a `multiple` filter, `None` when none are left.
+    pub fn normalize_multiple<T: Display + FromStr>(&self, values: &mut Option<Vec<T>>) {
+        if let Some(items) = values {
+            let items: Vec<_> = mem::take(items)
+                .into_iter()
+                .filter_map(|value| self.normalize_parsed(value))
+                .collect();
+            *values = (!items.is_empty()).then_some(items);
+        }
+    }
+
+    /// Splits comma separated values, `None` when there are none or they are all ignored,
+    /// as an empty list would match nothing.
+    ///
+    /// `\,` and `\\` are a comma and a backslash within a value, as written by `join`.
+    pub(crate) fn split<'a>(&self, value: &'a str) -> Option<Vec<Cow<'a, str>>> {
+        if value.is_empty() {
+            return None;
+        }
+        let items: Vec<_> = split_escaped(value)
+            .into_iter()
+            .filter_map(|item| match item {
+                Cow::Borrowed(item) => self.normalize(item),
+                Cow::Owned(item) => self
+                    .normalize(&item)
+                    .map(|item| Cow::Owned(item.into_owned())),
+            })
+            .collect();
+        (!items.is_empty()).then_some(items)
+    }
+
+    /// Checks the length of a normalized value and parses it.
+    pub(crate) fn parse<T>(&self, parser: impl Parser<T>, value: &str) -> Result<T, String> {
+        self.check_len(value)?;
+        parser.parse(value)
+    }
+
+    pub(crate) fn check_len(&self, value: &str) -> Result<(), String> {
+        match self.max_len {
+            Some(max_len) if value.chars().count() > max_len => Err(too_long(max_len)),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_count(&self, count: usize) -> Result<(), String> {
+        match self.max {
+            Some(max) if count > max => Err(too_many(max)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Joins values with commas, escaping their own commas and backslashes with a backslash.
+pub(crate) fn join<T: Display>(values: &[T]) -> String {
+    let values: Vec<_> = values
+        .iter()
+        .map(|value| value.to_string().replace('\\', "\\\\").replace(',', "\\,"))
+        .collect();
+    values.join(",")
+}
+
+/// Splits on the commas that are not escaped, see `join`.
+fn split_escaped(value: &str) -> Vec<Cow<'_, str>> {
+    let mut items = vec![];
+    // Set once an escaped character was found in the current item
+    let mut unescaped: Option<String> = None;
+    let mut start = 0;
+    let mut chars = value.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&(_, next @ (',' | '\\')))) => {
+                let item = unescaped.get_or_insert_with(String::new);
+                item.push_str(&value[start..i]);
+                item.push(next);
+                chars.next();
+                start = i + 2;
+            }
+            (',', _) => {
+                items.push(match unescaped.take() {
+                    Some(item) => Cow::Owned(item + &value[start..i]),
+                    None => Cow::Borrowed(&value[start..i]),
+                });
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(match unescaped {
+        Some(item) => Cow::Owned(item + &value[start..]),
+        None => Cow::Borrowed(&value[start..]),
+    });
+    items
+}
+
+/// How the values of a filter are parsed, once normalized.
+pub trait Parser<T> {
+    fn parse(&self, value: &str) -> Result<T, String>;
+}
+
+/// Parses the values with `FromStr`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Strict;
+
+impl<T> Parser<T> for Strict
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn parse(&self, value: &str) -> Result<T, String> {
+        value.parse().map_err(|err: T::Err| err.to_string())
+    }
+}
+
+/// Parses the values with [`LenientFromStr`], with `#[filter(lenient)]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lenient;
+
+impl<T: LenientFromStr> Parser<T> for Lenient {
+    fn parse(&self, value: &str) -> Result<T, String> {
+        T::from_str_lenient(value)
+    }
+}
+
+/// Parses the exclusive upper bound of `{field}_until` with
+/// [`LenientFromStr::from_str_lenient_end`], with `#[filter(lenient(range))]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LenientEnd;
+
+impl<T: LenientFromStr> Parser<T> for LenientEnd {
+    fn parse(&self, value: &str) -> Result<T, String> {
+        T::from_str_lenient_end(value)
+    }
+}
+
+pub fn too_long(max_len: usize) -> String {
+    format!("longer than {max_len} characters")
+}
+
+pub fn too_many(max: usize) -> String {
+    format!("more than {max} values")
+}
+
+/// Whether the value is at most `max_len` characters long, as written in a query string.
+pub fn within_len<T: Display>(value: &T, max_len: usize) -> bool {
+    value.to_string().chars().count() <= max_len
+}
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+pub use self::serde::{Multiple, Single};
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+mod serde {
+    use super::{FieldOptions, Parser, Strict};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+    use serde_with::{DeserializeAs, SerializeAs};
+    use std::{borrow::Cow, fmt::Display, marker::PhantomData};
+
+    const fn limit(limit: usize) -> Option<usize> {
+        if limit == usize::MAX {
+            None
+        } else {
+            Some(limit)
+        }
+    }
+
+    /// `serde_with` adapter of the single filters with options, parsed with `P`
+    /// as in a query string. `usize::MAX` means unlimited.
+    pub struct Single<
+        const MAX_LEN: usize,
+        const TRIM: bool,
+        const EMPTY_AS_NONE: bool,
+        const LOWERCASE: bool,
+        P = Strict,
+    >(PhantomData<P>);
+
+    impl<
+        'de,
+        T,
+        const MAX_LEN: usize,
+        const TRIM: bool,
+        const EMPTY_AS_NONE: bool,
+        const LOWERCASE: bool,
+        P: Parser<T> + Default,
+    > DeserializeAs<'de, Option<T>> for Single<MAX_LEN, TRIM, EMPTY_AS_NONE, LOWERCASE, P>
+    {
+        fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+            let options = FieldOptions {
+                max: None,
+                max_len: limit(MAX_LEN),
+                trim: TRIM,
+                empty_as_none: EMPTY_AS_NONE,
+                lowercase: LOWERCASE,
+            };
+            let Some(value) = Option::<Cow<'_, str>>::deserialize(deserializer)? else {
+                return Ok(None);
+            };
+            match options.normalize(&value) {
+                Some(value) => options
+                    .parse(P::default(), &value)
+                    .map(Some)
+                    .map_err(D::Error::custom),
+                None => Ok(None),
+            }
+        }
+    }
+
+    impl<
+        T,
+        const MAX_LEN: usize,
+        const TRIM: bool,
+        const EMPTY_AS_NONE: bool,
+        const LOWERCASE: bool,
+        P,
+    > SerializeAs<Option<T>> for Single<MAX_LEN, TRIM, EMPTY_AS_NONE, LOWERCASE, P>
+    where
+        T: Display,
+    {
+        fn serialize_as<S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value
+                .as_ref()
+                .map(ToString::to_string)
+                .serialize(serializer)
+        }
+    }
+
+    /// `serde_with` adapter of the `multiple` filters: comma separated values, which may
+    /// escape commas as `\,`, parsed with `P`. `usize::MAX` means unlimited.
+    pub struct Multiple<
+        const MAX: usize,
+        const MAX_LEN: usize,
+        const TRIM: bool,
+        const EMPTY_AS_NONE: bool,
+        const LOWERCASE: bool,
+        P = Strict,
+    >(PhantomData<P>);
+
+    impl<
+        'de,
+        T,
+        const MAX: usize,
+        const MAX_LEN: usize,
+        const TRIM: bool,
+        const EMPTY_AS_NONE: bool,
+        const LOWERCASE: bool,
+        P: Parser<T> + Default,
+    > DeserializeAs<'de, Option<Vec<T>>>
+        for Multiple<MAX, MAX_LEN, TRIM, EMPTY_AS_NONE, LOWERCASE, P>
+    {
+        fn deserialize_as<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<T>>, D::Error> {
+            let options = FieldOptions {
+                max: limit(MAX),
+                max_len: limit(MAX_LEN),
+                trim: TRIM,
+                empty_as_none: EMPTY_AS_NONE,
+                lowercase: LOWERCASE,
+            };
+            let Some(value) = Option::<Cow<'_, str>>::deserialize(deserializer)? else {
+                return Ok(None);
+            };
+            let Some(items) = options.split(&value) else {
+                return Ok(None);
+            };
+            options.check_count(items.len()).map_err(D::Error::custom)?;
+            items
+                .iter()
+                .map(|item| options.parse(P::default(), item).map_err(D::Error::custom))
+                .collect::<Result<_, _>>()
+                .map(Some)
+        }
+    }
+
+    impl<
+        T,
+        const MAX: usize,
+        const MAX_LEN: usize,
+        const TRIM: bool,
+        const EMPTY_AS_NONE: bool,
+        const LOWERCASE: bool,
+        P,
+    > SerializeAs<Option<Vec<T>>> for Multiple<MAX, MAX_LEN, TRIM, EMPTY_AS_NONE, LOWERCASE, P>
+    where
+        T: Display,
+    {
+        fn serialize_as<S: Serializer>(
+            values: &Option<Vec<T>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            values
+                .as_ref()
+                .map(|values| super::join(values))
+                .serialize(serializer)
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use diesel::{
+    dsl,
+    expression::{BoxableExpression, Expression},
+    pg::Pg,
+    prelude::*,
+    query_dsl::methods::FilterDsl,
+    sql_types::{Bool, Nullable},
+};
+
+/// A filter of the table `T`, boxed so that the filters of different types are applied
+/// the same way to selects, deletes and updates.
+pub type Predicate<'a, T> = Box<dyn BoxableExpression<T, Pg, SqlType = Nullable<Bool>> + 'a>;
+
+/// Used by the generated `filter` and its companion functions, not meant to be used directly.
+#[doc(hidden)]
+pub struct Predicates<'a, T> {
+    predicates: Vec<Predicate<'a, T>>,
+}
+
+impl<'a, T> Predicates<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            predicates: Vec::new(),
+        }
+    }
+
+    pub fn filter<E>(mut self, predicate: E) -> Self
+    where
+        E: Expression,
+        dsl::Nullable<E>: BoxableExpression<T, Pg, SqlType = Nullable<Bool>> + 'a,
+    {
+        // Only changes the SQL type, nothing is added to the query
+        self.predicates.push(Box::new(predicate.nullable()));
+        self
+    }
+
+    /// Filters `query` by every predicate, in order.
+    pub fn apply<Q>(self, query: Q) -> Q
+    where
+        Q: FilterDsl<Predicate<'a, T>, Output = Q>,
+    {
+        self.predicates.into_iter().fold(query, FilterDsl::filter)
+    }
+}
+
+impl<T> Default for Predicates<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,273 @@
+use crate::options::{self, FieldOptions, Parser};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use std::{borrow::Cow, error::Error, fmt, fmt::Display};
+
+// Everything but the RFC 3986 unreserved characters, spaces are written as `%20`
+// rather than `+` so that every framework decodes them the same way.
+const QUERY_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// How `#[filter(multiple)]` values are laid out in a query string.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum QueryStyle<'a> {
+    /// `?field=a,b` as parsed by the `actix` and `axum` integrations, commas and
+    /// backslashes within a value are escaped with a backslash.
+    #[default]
+    CommaSeparated,
+    /// `?prefix.field=a&prefix.field=b` as parsed by rocket's `FromForm`.
+    ///
+    /// Use `prefix: None` for handlers declared with `<filters..>`.
+    Form { prefix: Option<&'a str> },
+}
+
+/// The filters parsed by `from_query_str`, generated with `#[filter_struct(query_string)]`.
+///
+/// Required by the `actix` and `axum` extractors.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be parsed from a query string",
+    label = "not derived with `#[filter_struct(query_string)]`",
+    note = "add `#[filter_struct(query_string)]` to the model deriving `DieselFilter` \
+            to generate `from_query_str`"
+)]
+pub trait FromQueryStr: Sized {
+    fn from_query_str(query: &str) -> Result<Self, FilterParseError>;
+}
+
+/// Used by the generated `to_query_string` methods, not meant to be used directly.
+#[doc(hidden)]
+pub struct QueryWriter<'a> {
+    style: QueryStyle<'a>,
+    query: String,
+}
+
+impl<'a> QueryWriter<'a> {
+    pub fn new(style: QueryStyle<'a>) -> Self {
+        Self {
+            style,
+            query: String::new(),
+        }
+    }
+
+    fn key(&self, name: &str) -> String {
+        match self.style {
+            QueryStyle::Form {
+                prefix: Some(prefix),
+            } => format!("{prefix}.{name}"),
+            _ => name.to_owned(),
+        }
+    }
+
+    fn append_pair(&mut self, key: &str, value: &str) {
+        if !self.query.is_empty() {
+            self.query.push('&');
+        }
+        self.query.extend(utf8_percent_encode(key, QUERY_COMPONENT));
+        self.query.push('=');
+        self.query
+            .extend(utf8_percent_encode(value, QUERY_COMPONENT));
+    }
+
+    pub fn single<T: Display>(&mut self, name: &str, value: &Option<T>) {
+        if let Some(value) = value {
+            let key = self.key(name);
+            self.append_pair(&key, &value.to_string());
+        }
+    }
+
+    pub fn multiple<T: Display>(&mut self, name: &str, values: &Option<Vec<T>>) {
+        let Some(values) = values else {
+            return;
+        };
+        let key = self.key(name);
+
+        match self.style {
+            QueryStyle::CommaSeparated => {
+                self.append_pair(&key, &options::join(values));
+            }
+            QueryStyle::Form { .. } => {
+                for value in values {
+                    self.append_pair(&key, &value.to_string());
+                }
+            }
+        }
+    }
+
+    pub fn finish(self) -> String {
+        self.query
+    }
+}
+
+/// A query parameter that could not be parsed into its filter type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FieldError {
+    /// Name of the query parameter.
+    pub field: String,
+    /// The raw value that was received.
+    pub value: String,
+    /// Name of the type the value was parsed into, when known.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub expected: Option<String>,
+    /// Error returned by the type's `FromStr` implementation.
+    pub message: String,
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value `{}` for `{}`", self.value, self.field)?;
+        if let Some(expected) = &self.expected {
+            write!(f, ", expected {expected}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Returned by the generated `from_query_str` when some parameters are invalid.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FilterParseError {
+    pub errors: Vec<FieldError>,
+}
+
+impl Display for FilterParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            error.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for FilterParseError {}
+
+/// Splits a query string into decoded key/value pairs, a leading `?` is ignored.
+pub fn parse(query: &str) -> impl Iterator<Item = (Cow<'_, str>, Cow<'_, str>)> {
+    parse_pairs(query)
+}
+
+fn parse_pairs(query: &str) -> form_urlencoded::Parse<'_> {
+    let query = query.strip_prefix('?').unwrap_or(query);
+    form_urlencoded::parse(query.as_bytes())
+}
+
+/// Used by the generated `from_query_str` methods, not meant to be used directly.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct QueryReader {
+    errors: Vec<FieldError>,
+}
+
+impl QueryReader {
+    fn push_error(&mut self, name: &str, expected: &str, value: &str, message: String) {
+        self.errors.push(FieldError {
+            field: name.to_owned(),
+            value: value.to_owned(),
+            expected: Some(expected.to_owned()),
+            message,
+        });
+    }
+
+    pub fn single<T>(
+        &mut self,
+        name: &str,
+        expected: &str,
+        value: &str,
+        options: FieldOptions,
+        parser: impl Parser<T>,
+        target: &mut Option<T>,
+    ) {
+        let Some(normalized) = options.normalize(value) else {
+            return;
+        };
+        match options.parse(parser, &normalized) {
+            Ok(parsed) => *target = Some(parsed),
+            Err(message) => self.push_error(name, expected, value, message),
+        }
+    }
+
+    /// Comma separated values and repeated keys are both appended to the list.
+    pub fn multiple<T>(
+        &mut self,
+        name: &str,
+        expected: &str,
+        value: &str,
+        options: FieldOptions,
+        parser: impl Parser<T> + Copy,
+        target: &mut Option<Vec<T>>,
+    ) {
+        let Some(items) = options.split(value) else {
+            return;
+        };
+        let values = target.get_or_insert_with(Vec::new);
+        if let Err(message) = options.check_count(values.len() + items.len()) {
+            // Reported once when the values are spread over repeated keys
+            if !self
+                .errors
+                .iter()
+                .any(|error| error.field == name && error.message == message)
+            {
+                self.push_error(name, expected, value, message);
+            }
+            return;
+        }
+        for item in items {
+            match options.parse(parser, &item) {
+                Ok(parsed) => values.push(parsed),
+                Err(message) => self.push_error(name, expected, &item, message),
+            }
+        }
+    }
+
+    pub fn finish<T>(self, value: T) -> Result<T, FilterParseError> {
+        if self.errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(FilterParseError {
+                errors: self.errors,
+            })
+        }
+    }
+}
+
+/// Parses filters and pagination parameters from the same query string,
+/// errors from both are reported together.
+#[cfg(any(feature = "actix", feature = "axum"))]
+pub(crate) fn parse_list_query<F, P>(query: &str) -> Result<(F, P), FilterParseError>
+where
+    F: FromQueryStr,
+    P: serde::de::DeserializeOwned,
+{
+    let filters = F::from_query_str(query);
+    let pagination: Result<P, _> =
+        serde_path_to_error::deserialize(serde_urlencoded::Deserializer::new(parse_pairs(query)));
+
+    match (filters, pagination) {
+        (Ok(filters), Ok(pagination)) => Ok((filters, pagination)),
+        (filters, pagination) => {
+            let mut errors = filters.err().unwrap_or_default().errors;
+            if let Err(err) = pagination {
+                let field = err.path().to_string();
+                let value = parse(query)
+                    .filter(|(key, _)| *key == field)
+                    .map(|(_, value)| value.into_owned())
+                    .last()
+                    .unwrap_or_default();
+                errors.push(FieldError {
+                    field,
+                    value,
+                    expected: None,
+                    message: err.into_inner().to_string(),
+                });
+            }
+            Err(FilterParseError { errors })
+        }
+    }
+}
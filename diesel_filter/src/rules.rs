@@ -0,0 +1,37 @@
+use std::{error::Error, fmt, fmt::Display};
+
+/// Returned by the generated `validate`, and by `filter` and its companion functions,
+/// when the filters break a `#[filter_struct(...)]` rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "rule", rename_all = "snake_case")
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum FilterRuleError {
+    /// None of the `require_any` filters is set.
+    RequireAny { fields: Vec<&'static str> },
+    /// More than one of the `exclusive` filters is set, `fields` are the ones that are.
+    Exclusive { fields: Vec<&'static str> },
+}
+
+impl Display for FilterRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (message, fields) = match self {
+            Self::RequireAny { fields } => ("one of these filters is required", fields),
+            Self::Exclusive { fields } => ("these filters can't be used together", fields),
+        };
+        write!(f, "{message}: ")?;
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{field}`")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for FilterRuleError {}
@@ -1,8 +1,14 @@
-use darling::{FromDeriveInput, FromField, FromMeta, ast, util::Ignored};
+use darling::{
+    FromDeriveInput, FromField, FromMeta,
+    ast::{self, NestedMeta},
+    util::{Ignored, Override, PathList},
+};
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span};
-use quote::{ToTokens, quote};
-use syn::{Attribute, DeriveInput, Meta, Type, parse_macro_input};
+use proc_macro2::Ident;
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Attribute, DeriveInput, Expr, Meta, Type, ext::IdentExt, parse_macro_input, parse_quote,
+};
 
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(diesel))]
@@ -17,8 +23,71 @@ struct DieselFilterAttrs {
     data: ast::Data<Ignored, DieselFilterField>,
 }
 
+/// Predicates added to every query, e.g. `#[filter_scope(is_null = deleted_at, eq = tenant_id)]`.
+///
+/// The values of `eq` columns are required arguments of the generated functions.
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(filter_scope))]
+struct FilterScopeAttrs {
+    #[darling(default, multiple)]
+    is_null: Vec<Ident>,
+    #[darling(default, multiple)]
+    is_not_null: Vec<Ident>,
+    #[darling(default, multiple)]
+    eq: Vec<Ident>,
+}
+
+/// Rules checked by the generated `validate`,
+/// e.g. `#[filter_struct(require_any(owner_id, email), exclusive(name, text))]`,
+/// normalization options applying to every filter and opt-in generated functions.
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(filter_struct))]
+struct FilterStructAttrs {
+    #[darling(default)]
+    require_any: Option<PathList>,
+    #[darling(default, multiple)]
+    exclusive: Vec<PathList>,
+    #[darling(flatten)]
+    normalize: NormalizeAttrs,
+    /// Time zone of the `date` filters without their own.
+    #[darling(default)]
+    tz: Option<Expr>,
+    /// Generates `to_query_string` and `from_query_str`, which need the filtered types
+    /// to implement `Display` and `FromStr`.
+    #[darling(default)]
+    query_string: bool,
+    /// Generates `matches`, which needs the filtered types to implement `PartialEq`.
+    #[darling(default)]
+    matches: bool,
+}
+
+/// `trim`, `empty_as_none` and `lowercase`, on a filter or on the whole struct.
+#[derive(Debug, Clone, Copy, FromMeta, Default)]
+struct NormalizeAttrs {
+    #[darling(default)]
+    trim: bool,
+    #[darling(default)]
+    empty_as_none: bool,
+    #[darling(default)]
+    lowercase: bool,
+}
+
+impl NormalizeAttrs {
+    fn or(self, other: Self) -> Self {
+        Self {
+            trim: self.trim || other.trim,
+            empty_as_none: self.empty_as_none || other.empty_as_none,
+            lowercase: self.lowercase || other.lowercase,
+        }
+    }
+
+    fn any(self) -> bool {
+        self.trim || self.empty_as_none || self.lowercase
+    }
+}
+
 #[derive(Debug, FromField)]
-#[darling(forward_attrs(filter))]
+#[darling(forward_attrs(filter, doc))]
 struct DieselFilterField {
     ident: Option<Ident>,
     ty: Type,
@@ -28,11 +97,143 @@ struct DieselFilterField {
 #[derive(Debug, FromMeta, Default)]
 struct DieselFilterFieldAttrs {
     #[darling(default)]
-    multiple: bool,
+    multiple: Multiple,
     #[darling(default)]
     insensitive: bool,
     #[darling(default)]
     substring: bool,
+    /// Maximum number of characters of each value.
+    #[darling(default)]
+    max_len: Option<usize>,
+    #[darling(flatten)]
+    normalize: NormalizeAttrs,
+    /// Parses the values with `LenientFromStr` rather than `FromStr`.
+    #[darling(default)]
+    lenient: Option<Override<LenientAttrs>>,
+    #[darling(default)]
+    date: Option<Override<DateAttrs>>,
+    #[darling(default)]
+    array: Option<ArrayAttrs>,
+    #[darling(default)]
+    jsonb: Option<Override<JsonbAttrs>>,
+}
+
+/// `#[filter(lenient)]` or `#[filter(lenient(range))]`.
+#[derive(Debug, Clone, Copy, FromMeta, Default)]
+struct LenientAttrs {
+    /// `{field}_since` and `{field}_until`, date-only values of date-times widened to
+    /// the whole day.
+    #[darling(default)]
+    range: bool,
+}
+
+/// `#[filter(date)]` or `#[filter(date(tz = chrono_tz::Europe::Paris))]`.
+#[derive(Debug, Clone, FromMeta, Default)]
+struct DateAttrs {
+    /// Any `chrono::TimeZone`, `Utc` by default.
+    #[darling(default)]
+    tz: Option<Expr>,
+}
+
+/// `#[filter(array(contains, overlaps, contained_by))]` on a `Vec` backed by a Postgres array.
+#[derive(Debug, Clone, FromMeta, Default)]
+struct ArrayAttrs {
+    /// `{field}_contains`, all the values, `@>`.
+    #[darling(default)]
+    contains: bool,
+    /// `{field}_overlaps`, any of the values, `&&`.
+    #[darling(default)]
+    overlaps: bool,
+    /// `{field}_contained_by`, only the values, `<@`.
+    #[darling(default)]
+    contained_by: bool,
+    /// Maximum number of values of each filter.
+    #[darling(default)]
+    max: Option<usize>,
+}
+
+/// `#[filter(jsonb)]` or `#[filter(jsonb(path = "address.city"))]` on a `serde_json::Value`.
+#[derive(Debug, Clone, FromMeta, Default)]
+struct JsonbAttrs {
+    /// Keys compared as text, e.g. `address.city` as the `{field}_address_city` filter.
+    #[darling(default, multiple, rename = "path")]
+    paths: Vec<JsonbPath>,
+    /// Whether `{field}_has_key` takes several keys (`?|`), as the field is `multiple`
+    /// by default.
+    #[darling(default)]
+    has_any_key: Option<bool>,
+}
+
+/// `path = "address.city"`, or `path(key = "address.city", name = "city")` for the
+/// `{field}_city` filter.
+#[derive(Debug, Clone)]
+struct JsonbPath {
+    key: String,
+    name: Option<String>,
+}
+
+impl JsonbPath {
+    /// The suffix of the filter, the key with `_` for the characters not allowed in names.
+    fn suffix(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => self
+                .key
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect(),
+        }
+    }
+}
+
+impl FromMeta for JsonbPath {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self {
+            key: value.to_owned(),
+            name: None,
+        })
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct NamedPath {
+            key: String,
+            name: String,
+        }
+
+        let NamedPath { key, name } = NamedPath::from_list(items)?;
+        Ok(Self {
+            key,
+            name: Some(name),
+        })
+    }
+}
+
+/// `#[filter(multiple(max = 100))]`.
+#[derive(Debug, Clone, FromMeta, Default)]
+struct MultipleAttrs {
+    /// Maximum number of values.
+    #[darling(default)]
+    max: Option<usize>,
+}
+
+/// `#[filter(multiple)]`, `#[filter(multiple = true)]` as before the limits,
+/// or `#[filter(multiple(max = 100))]`.
+#[derive(Debug, Clone, Default)]
+struct Multiple(Option<MultipleAttrs>);
+
+impl FromMeta for Multiple {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self(Some(Default::default())))
+    }
+
+    fn from_bool(value: bool) -> darling::Result<Self> {
+        Ok(Self(value.then(Default::default)))
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        MultipleAttrs::from_list(items).map(|attrs| Self(Some(attrs)))
+    }
 }
 
 struct DieselFilterFieldAttrsOuter(DieselFilterFieldAttrs);
@@ -52,6 +253,16 @@ impl FromMeta for DieselFilterFieldAttrsOuter {
 
 // https://stackoverflow.com/a/77040924/746914
 fn option_type(ty: &Type) -> Option<&Type> {
+    generic_type(ty, "Option", &["core", "std"], "option")
+}
+
+/// The values of an `array` filter.
+fn vec_type(ty: &Type) -> Option<&Type> {
+    generic_type(ty, "Vec", &["alloc", "std"], "vec")
+}
+
+/// The type parameter of e.g. `Option<T>` or `std::option::Option<T>`.
+fn generic_type<'a>(ty: &'a Type, name: &str, crates: &[&str], module: &str) -> Option<&'a Type> {
     let Type::Path(ty) = ty else { return None };
     if ty.qself.is_some() {
         return None;
@@ -59,14 +270,14 @@ fn option_type(ty: &Type) -> Option<&Type> {
 
     let ty = &ty.path;
 
-    if ty.segments.is_empty() || ty.segments.last().unwrap().ident != "Option" {
+    if ty.segments.is_empty() || ty.segments.last().unwrap().ident != name {
         return None;
     }
 
     if !(ty.segments.len() == 1
         || (ty.segments.len() == 3
-            && ["core", "std"].contains(&ty.segments[0].ident.to_string().as_str())
-            && ty.segments[1].ident == "option"))
+            && crates.contains(&ty.segments[0].ident.to_string().as_str())
+            && ty.segments[1].ident == module))
     {
         return None;
     }
@@ -85,7 +296,42 @@ fn option_type(ty: &Type) -> Option<&Type> {
     Some(inner_type)
 }
 
-#[proc_macro_derive(DieselFilter, attributes(filter, table_name))]
+/// A limit given as a const generic parameter, `usize::MAX` meaning unlimited.
+#[cfg(any(feature = "actix", feature = "axum"))]
+fn const_limit(value: Option<usize>) -> String {
+    value.map_or("{ usize::MAX }".to_owned(), |value| value.to_string())
+}
+
+fn option_tokens(value: Option<usize>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+/// Joins the lines of `///` comments, as rustdoc does.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(doc),
+                    ..
+                }) => Some(doc.value().trim().to_owned()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    (!lines.is_empty()).then(|| lines.join("\n").trim().to_owned())
+}
+
+#[proc_macro_derive(
+    DieselFilter,
+    attributes(filter, filter_scope, filter_struct, table_name)
+)]
 pub fn diesel_filter_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -102,16 +348,112 @@ pub fn diesel_filter_derive(input: TokenStream) -> TokenStream {
         Err(err) => return err.write_errors().into(),
     };
 
+    let scope = match FilterScopeAttrs::from_derive_input(&input) {
+        Ok(ok) => ok,
+        Err(err) => return err.write_errors().into(),
+    };
+
+    let rules = match FilterStructAttrs::from_derive_input(&input) {
+        Ok(ok) => ok,
+        Err(err) => return err.write_errors().into(),
+    };
+
     let filter_struct_ident = Ident::new(&format!("{struct_ident}Filters"), struct_ident.span());
+    let struct_fields = data.take_struct().expect("Expected struct data").fields;
 
     let mut errors = vec![];
-    let mut fields = vec![];
+    let mut scope_params = vec![];
+    let mut scope_args = vec![];
+    let mut scope_queries = vec![];
+    let mut scope_checks = vec![];
+    let mut matches_params = vec![];
+
+    // The model fields are needed by `matches`, and give the type of `eq` arguments
+    let scope_field = |column: &Ident| {
+        let field = struct_fields
+            .iter()
+            .find(|field| field.ident.as_ref() == Some(column))
+            .ok_or_else(|| {
+                darling::Error::custom("`filter_scope` columns must be fields of the model")
+                    .with_span(column)
+            })?;
+        let nullable = option_type(&field.ty).is_some();
+        Ok::<_, darling::Error>((option_type(&field.ty).unwrap_or(&field.ty), nullable))
+    };
+
+    let null_scopes = (scope.is_null.iter().map(|column| (column, true)))
+        .chain(scope.is_not_null.iter().map(|column| (column, false)));
+    for (column, is_null) in null_scopes {
+        let nullable = match scope_field(column) {
+            Ok((_, nullable)) => nullable,
+            Err(err) => {
+                errors.push(err.write_errors());
+                continue;
+            }
+        };
+        let (predicate, check) = match (is_null, nullable) {
+            (true, true) => (quote!(is_null), quote!(model.#column.is_none())),
+            (true, false) => (quote!(is_null), quote!(false)),
+            (false, true) => (quote!(is_not_null), quote!(model.#column.is_some())),
+            (false, false) => (quote!(is_not_null), quote!(true)),
+        };
+        scope_queries.push(quote! {
+            query = query.filter(#table_name::#column.#predicate());
+        });
+        scope_checks.push(quote! {
+            if !(#check) {
+                return false;
+            }
+        });
+    }
+
+    for column in &scope.eq {
+        let (ty, nullable) = match scope_field(column) {
+            Ok(ok) => ok,
+            Err(err) => {
+                errors.push(err.write_errors());
+                continue;
+            }
+        };
+        scope_params.push(quote!(#column: #ty,));
+        scope_args.push(quote!(#column,));
+        matches_params.push(quote!(#column: &#ty,));
+        scope_queries.push(quote! {
+            query = query.filter(#table_name::#column.eq(#column));
+        });
+        let check = if nullable {
+            quote!(model.#column.as_ref() == Some(#column))
+        } else {
+            quote!(&model.#column == #column)
+        };
+        scope_checks.push(quote! {
+            if !(#check) {
+                return false;
+            }
+        });
+    }
+
+    // Attributes, `rocket` attributes, name and type of each field of the filters struct
+    let mut filter_fields: Vec<(_, Vec<proc_macro2::TokenStream>, Ident, _)> = vec![];
+    let mut normalizations = vec![];
+    // Functions normalizing the values parsed by `async-graphql`, see `process_with` below
+    #[allow(unused_mut)]
+    let mut graphql_normalizations: Vec<proc_macro2::TokenStream> = vec![];
     let mut queries = vec![];
     let mut uses = vec![];
+    let mut writes = vec![];
+    let mut reads = vec![];
+    let mut descriptors = vec![];
+    let mut checks = vec![];
     let mut has_multiple = false;
+    let mut filter_params = vec![];
 
-    for field_filter in data.take_struct().expect("Expected struct data").fields {
-        let Some(attr) = field_filter.attrs.into_iter().next() else {
+    for field_filter in struct_fields {
+        let (filter_attrs, docs): (Vec<_>, Vec<_>) = field_filter
+            .attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("filter"));
+        let Some(attr) = filter_attrs.into_iter().next() else {
             continue;
         };
         let filter_attr = match DieselFilterFieldAttrsOuter::from_meta(&attr.meta) {
@@ -125,94 +467,1071 @@ pub fn diesel_filter_derive(input: TokenStream) -> TokenStream {
         let field = field_filter
             .ident
             .expect("Expected all fields to have identifiers");
+        let param = field.unraw().to_string();
 
+        let nullable = option_type(&field_filter.ty).is_some();
         let ty = option_type(&field_filter.ty)
             .unwrap_or(&field_filter.ty)
             .to_owned();
-        let ty: Ident = Ident::new(
-            ty.to_token_stream().to_string().replace(' ', "").as_str(),
-            Span::call_site(),
-        );
+        // The filters of `array` fields take values of the array
+        let ty = match (&filter_attr.array, vec_type(&ty)) {
+            (Some(_), Some(value)) => value.to_owned(),
+            (Some(_), None) => {
+                errors.push(
+                    darling::Error::custom("`array` filters must be on `Vec` fields")
+                        .with_span(&field)
+                        .write_errors(),
+                );
+                continue;
+            }
+            (None, _) => ty,
+        };
+        // The keys of `jsonb` fields are compared as text
+        let ty = match &filter_attr.jsonb {
+            Some(_) => parse_quote!(String),
+            None => ty,
+        };
+        let expected = ty.to_token_stream().to_string().replace(' ', "");
 
-        let q = if filter_attr.multiple {
-            has_multiple = true;
+        let (multiple, max) = match &filter_attr.multiple.0 {
+            Some(multiple) => (true, multiple.max),
+            None => (false, None),
+        };
+        let max_len = filter_attr.max_len;
+        let (max_tokens, max_len_tokens) = (option_tokens(max), option_tokens(max_len));
+        let normalize = filter_attr.normalize.or(rules.normalize);
+        let NormalizeAttrs {
+            trim,
+            empty_as_none,
+            lowercase,
+        } = normalize;
+        let field_options = |max: Option<usize>| {
+            let max = option_tokens(max);
+            quote! {
+                ::diesel_filter::options::FieldOptions {
+                    max: #max,
+                    max_len: #max_len_tokens,
+                    trim: #trim,
+                    empty_as_none: #empty_as_none,
+                    lowercase: #lowercase,
+                }
+            }
+        };
+        let normalize_func = |multiple: bool| {
+            if multiple {
+                quote!(normalize_multiple)
+            } else {
+                quote!(normalize_single)
+            }
+        };
+        let lenient = filter_attr.lenient.is_some();
+        let parser = if lenient {
+            quote!(::diesel_filter::options::Lenient)
+        } else {
+            quote!(::diesel_filter::options::Strict)
+        };
 
+        // Attributes and `rocket` attributes of the field of a single or multiple filter
+        #[allow(unused_variables, unused_mut)]
+        let mut attributes = |field: &Ident, multiple: bool, max: Option<usize>| {
             #[allow(unused_mut)]
             let mut field_attributes: Vec<proc_macro2::TokenStream> = vec![];
+            // Moved to the struct deriving `FromForm` when some values are normalized, see below
+            #[allow(unused_mut)]
+            let mut rocket_attributes: Vec<proc_macro2::TokenStream> = vec![];
 
             #[cfg(feature = "utoipa")]
-            field_attributes.push(quote! { #[param(value_type = String)] });
+            {
+                if let Some(max) = max {
+                    field_attributes.push(quote! { #[param(max_items = #max)] });
+                }
+                if let Some(max_len) = max_len {
+                    field_attributes.push(quote! { #[param(max_length = #max_len)] });
+                }
+            }
+
+            // The length of the array, or of the string of single filters
+            #[cfg(feature = "schemars")]
+            if let Some(max) = if multiple { max } else { max_len } {
+                field_attributes.push(quote! { #[schemars(length(max = #max))] });
+            }
 
             #[cfg(feature = "rocket")]
-            field_attributes.push(quote! { #[field(default = Option::None)] });
+            {
+                if let Some(max) = max {
+                    rocket_attributes.push(quote! { #[field(validate = len(..=#max))] });
+                }
+                if let Some(max_len) = max_len {
+                    let values = if multiple {
+                        quote!(values.iter().flatten())
+                    } else {
+                        quote!(values.iter())
+                    };
+                    rocket_attributes.push(quote! {
+                        #[field(validate = with(
+                            |values| #values.all(|value| ::diesel_filter::options::within_len(value, #max_len)),
+                            ::diesel_filter::options::too_long(#max_len),
+                        ))]
+                    });
+                }
+            }
 
-            #[cfg(any(feature = "actix", feature = "axum"))]
+            #[cfg(feature = "async-graphql")]
+            if max.is_some() || max_len.is_some() {
+                let mut validators = vec![];
+                if let Some(max) = max {
+                    validators.push(quote!(max_items = #max));
+                }
+                if let Some(max_len) = max_len {
+                    if multiple {
+                        validators.push(quote!(list));
+                    }
+                    validators.push(quote! {
+                        custom = |value: &#ty| {
+                            if ::diesel_filter::options::within_len(value, #max_len) {
+                                Ok(())
+                            } else {
+                                Err(::diesel_filter::options::too_long(#max_len))
+                            }
+                        }
+                    });
+                }
+                field_attributes.push(quote! { #[graphql(validator(#( #validators ),*))] });
+            }
+
+            #[cfg(feature = "async-graphql")]
+            if normalize.any() {
+                let options = field_options(max);
+                let normalize_func = normalize_func(multiple);
+                let value_ty = if multiple {
+                    quote!(Option<Vec<T>>)
+                } else {
+                    quote!(Option<T>)
+                };
+                let func = format_ident!("graphql_normalize_{}", field);
+                graphql_normalizations.push(quote! {
+                    fn #func<T: ::std::fmt::Display + ::std::str::FromStr>(value: &mut #value_ty) {
+                        #options.#normalize_func(value)
+                    }
+                });
+                field_attributes.push(quote! { #[graphql(process_with = Self::#func)] });
+            }
+
+            if multiple {
+                // Comma separated, except with rocket alone which expects repeated keys
+                #[cfg(feature = "utoipa")]
+                {
+                    let explode =
+                        cfg!(feature = "rocket") && !cfg!(any(feature = "actix", feature = "axum"));
+                    field_attributes.push(quote! { #[param(style = Form, explode = #explode)] });
+                }
+
+                #[cfg(feature = "rocket")]
+                rocket_attributes.push(quote! { #[field(default = Option::None)] });
+
+                // Not the schema of the comma separated string `serde_as` deserializes
+                #[cfg(feature = "schemars")]
+                {
+                    let schema_type = format!("Option<Vec<{expected}>>");
+                    field_attributes.push(quote! { #[schemars(with = #schema_type)] });
+                }
+
+                // Split as by `from_query_str`, with the same escaped commas
+                #[cfg(any(feature = "actix", feature = "axum"))]
+                {
+                    let serde_as_path = format!(
+                        "::diesel_filter::options::Multiple<{}, {}, {trim}, {empty_as_none}, {lowercase}, {parser}>",
+                        const_limit(max),
+                        const_limit(max_len),
+                    );
+                    field_attributes.push(quote! {
+                        #[serde_as(as = #serde_as_path)]
+                        #[serde(default)]
+                    });
+                }
+            } else {
+                // Checked on the raw value, parsed as by `from_query_str`
+                #[cfg(any(feature = "actix", feature = "axum"))]
+                if max_len.is_some() || normalize.any() || lenient {
+                    let serde_as_path = format!(
+                        "::diesel_filter::options::Single<{}, {trim}, {empty_as_none}, {lowercase}, {parser}>",
+                        const_limit(max_len),
+                    );
+                    field_attributes.push(quote! {
+                        #[serde_as(as = #serde_as_path)]
+                        #[serde(default)]
+                    });
+
+                    #[cfg(feature = "schemars")]
+                    {
+                        let schema_type = format!("Option<{expected}>");
+                        field_attributes.push(quote! { #[schemars(with = #schema_type)] });
+                    }
+                }
+            }
+
+            (field_attributes, rocket_attributes)
+        };
+
+        let value = if nullable {
+            quote!(let Some(value) = &model.#field else { return false; };)
+        } else {
+            quote!(let value = &model.#field;)
+        };
+
+        // `{field}_contains`, `{field}_overlaps` and `{field}_contained_by`, rather than
+        // the equality filter
+        if let Some(array) = &filter_attr.array {
+            if multiple
+                || filter_attr.substring
+                || filter_attr.insensitive
+                || filter_attr.date.is_some()
+                || filter_attr.jsonb.is_some()
             {
-                let serde_as_path = format!(
-                    "Option<::diesel_filter::serde_with::StringWithSeparator::<::diesel_filter::serde_with::formats::CommaSeparator, {}>>",
-                    ty
+                errors.push(
+                    darling::Error::custom(
+                        "`array` filters can't be `multiple`, `substring`, `insensitive`, `date` or `jsonb`",
+                    )
+                    .with_span(&field)
+                    .write_errors(),
                 );
-                field_attributes.push(quote! { #[serde_as(as = #serde_as_path)] });
+                continue;
+            }
+            if !(array.contains || array.overlaps || array.contained_by) {
+                errors.push(
+                    darling::Error::custom(
+                        "`array` expects `contains`, `overlaps` and/or `contained_by`",
+                    )
+                    .with_span(&field)
+                    .write_errors(),
+                );
+                continue;
             }
 
-            fields.push(quote! {
-                #( #field_attributes )*
-                pub #field: Option<Vec<#ty>>,
-            });
+            let column = quote!(#table_name::#field);
+            let array_filters = [
+                (
+                    "contains",
+                    array.contains,
+                    quote!(Contains),
+                    "Matches the arrays containing all the values.",
+                    quote!(#column.contains(filter)),
+                    quote!(filters.iter().all(|filter| value.contains(filter))),
+                ),
+                (
+                    "overlaps",
+                    array.overlaps,
+                    quote!(Overlaps),
+                    "Matches the arrays containing any of the values.",
+                    quote!(#column.overlaps_with(filter)),
+                    quote!(filters.iter().any(|filter| value.contains(filter))),
+                ),
+                (
+                    "contained_by",
+                    array.contained_by,
+                    quote!(ContainedBy),
+                    "Matches the arrays containing only these values.",
+                    quote!(#column.is_contained_by(filter)),
+                    quote!(value.iter().all(|value| filters.contains(value))),
+                ),
+            ];
+            let options = field_options(array.max);
+            let max_tokens = option_tokens(array.max);
+            for (suffix, enabled, operator, doc, q, check) in array_filters {
+                if !enabled {
+                    continue;
+                }
+                let array_field = format_ident!("{}_{}", param, suffix);
+                let array_param = format!("{param}_{suffix}");
+                filter_params.push((array_field.clone(), array_param.clone(), true));
 
-            match (filter_attr.insensitive, filter_attr.substring) {
-                (false, false) => {
-                    quote! { #table_name::#field.eq(any(filter)) }
+                if normalize.any() {
+                    normalizations
+                        .push(quote! { #options.normalize_multiple(&mut filters.#array_field); });
                 }
-                (false, true) => {
+                let (field_attributes, rocket_attributes) =
+                    attributes(&array_field, true, array.max);
+                filter_fields.push((
                     quote! {
-                        #table_name::#field.like(any(
-                            filter.iter().map(|f| format!("%{}%", f)).collect::<Vec<_>>()
-                        ))
+                        #[doc = #doc]
+                        #( #field_attributes )*
+                    },
+                    rocket_attributes,
+                    array_field.clone(),
+                    quote!(Option<Vec<#ty>>),
+                ));
+                writes.push(quote! { writer.multiple(#array_param, &self.#array_field); });
+                reads.push(quote! {
+                    #array_param => reader.multiple(#array_param, #expected, &value, #options, #parser, &mut filters.#array_field),
+                });
+                queries.push(quote! {
+                    if let Some(filter) = filters.#array_field {
+                        query = query.filter(#q);
+                    }
+                });
+                checks.push(quote! {
+                    if let Some(filters) = &self.#array_field {
+                        #value
+                        if !(#check) {
+                            return false;
+                        }
                     }
+                });
+                descriptors.push(quote! {
+                    ::diesel_filter::FilterField {
+                        name: #array_param,
+                        value_type: #expected,
+                        operators: &[::diesel_filter::FilterOperator::#operator],
+                        nullable: #nullable,
+                        max: #max_tokens,
+                        max_len: #max_len_tokens,
+                        description: Some(#doc),
+                    },
+                });
+            }
+            continue;
+        }
+
+        if filter_attr.jsonb.is_some() && filter_attr.date.is_some() {
+            errors.push(
+                darling::Error::custom("`jsonb` filters can't be `date`")
+                    .with_span(&field)
+                    .write_errors(),
+            );
+            continue;
+        }
+
+        // The filter of the field, or those of the `path`s of a `jsonb` field
+        let targets = match &filter_attr.jsonb {
+            None => vec![(
+                field.clone(),
+                param.clone(),
+                quote!(#table_name::#field),
+                value.clone(),
+                nullable,
+                quote!(#( #docs )*),
+                match doc_comment(&docs) {
+                    Some(doc) => quote!(Some(#doc)),
+                    None => quote!(None),
+                },
+            )],
+            Some(jsonb) => {
+                let paths = jsonb.clone().unwrap_or_default().paths;
+                // Different keys can have the same suffix, e.g. `a.b` and `a_b`
+                let mut suffixes = vec!["contains".to_owned(), "has_key".to_owned()];
+                let mut invalid = false;
+                for path in &paths {
+                    let suffix = path.suffix();
+                    let message = if syn::parse_str::<Ident>(&format!("{param}_{suffix}")).is_err()
+                    {
+                        format!(
+                            "the `{param}_{suffix}` filter of the `{}` path isn't a valid name",
+                            path.key
+                        )
+                    } else if suffixes.contains(&suffix) {
+                        format!(
+                            "the `{}` path is another `{param}_{suffix}` filter, \
+                             name it with `path(key = \"{}\", name = \"...\")`",
+                            path.key, path.key
+                        )
+                    } else {
+                        suffixes.push(suffix);
+                        continue;
+                    };
+                    errors.push(
+                        darling::Error::custom(message)
+                            .with_span(&field)
+                            .write_errors(),
+                    );
+                    invalid = true;
                 }
-                (true, false) => {
-                    quote! { #table_name::#field.ilike(any(filter)) }
+                if invalid {
+                    continue;
+                }
+
+                paths
+                    .iter()
+                    .map(|path| {
+                        let suffix = path.suffix();
+                        let path = &path.key;
+                        let keys: Vec<_> = path.split('.').collect();
+                        let json = if nullable {
+                            quote!(model.#field.as_ref())
+                        } else {
+                            quote!(Some(&model.#field))
+                        };
+                        let doc = format!("Compared to the text at `{path}`.");
+                        (
+                            format_ident!("{}_{}", param, suffix),
+                            format!("{param}_{suffix}"),
+                            quote!(#table_name::#field.retrieve_by_path_as_text(vec![#( #keys ),*])),
+                            quote! {
+                                let value = #json.and_then(|json| {
+                                    ::diesel_filter::json::path_text(json, &[#( #keys ),*])
+                                });
+                                let Some(value) = &value else { return false; };
+                            },
+                            true,
+                            quote!(#[doc = #doc]),
+                            quote!(Some(#doc)),
+                        )
+                    })
+                    .collect()
+            }
+        };
+        for (
+            filter_field,
+            filter_param,
+            column,
+            target_value,
+            nullable,
+            doc_attributes,
+            description,
+        ) in targets
+        {
+            filter_params.push((filter_field.clone(), filter_param.clone(), multiple));
+            let options = field_options(max);
+            if normalize.any() {
+                let normalize_func = normalize_func(multiple);
+                normalizations
+                    .push(quote! { #options.#normalize_func(&mut filters.#filter_field); });
+            }
+
+            let mut operators = vec![];
+            if filter_attr.substring {
+                operators.push(quote!(Substring));
+            } else {
+                operators.push(quote!(Eq));
+            }
+            if filter_attr.insensitive {
+                operators.push(quote!(Insensitive));
+            }
+            if multiple {
+                operators.push(quote!(Multiple));
+            }
+            // Same semantics as the SQL of `filter`, a NULL column matches nothing
+            let pattern = match (filter_attr.substring, filter_attr.insensitive) {
+                (true, _) => Some(quote!(format!("%{}%", filter))),
+                (false, true) => Some(quote!(filter.to_string())),
+                (false, false) => None,
+            };
+            let insensitive = filter_attr.insensitive;
+            let check = match (multiple, pattern) {
+                (false, None) => quote!(value == filter),
+                (true, None) => quote!(filters.contains(value)),
+                (false, Some(pattern)) => quote! {
+                    ::diesel_filter::matching::like(&value.to_string(), &#pattern, #insensitive)
+                },
+                (true, Some(pattern)) => quote! {
+                    filters.iter().any(|filter| {
+                        ::diesel_filter::matching::like(&value.to_string(), &#pattern, #insensitive)
+                    })
+                },
+            };
+            // An empty list matches nothing, as `= ANY('{}')` does
+            let binding = if multiple {
+                quote!(filters)
+            } else {
+                quote!(filter)
+            };
+            checks.push(quote! {
+                if let Some(#binding) = &self.#filter_field {
+                    #target_value
+                    if !(#check) {
+                        return false;
+                    }
                 }
-                (true, true) => {
+            });
+
+            descriptors.push(quote! {
+                ::diesel_filter::FilterField {
+                    name: #filter_param,
+                    value_type: #expected,
+                    operators: &[#( ::diesel_filter::FilterOperator::#operators ),*],
+                    nullable: #nullable,
+                    max: #max_tokens,
+                    max_len: #max_len_tokens,
+                    description: #description,
+                },
+            });
+
+            let (field_attributes, rocket_attributes) = attributes(&filter_field, multiple, max);
+            let q = if multiple {
+                has_multiple = true;
+
+                filter_fields.push((
                     quote! {
-                        #table_name::#field.ilike(any(
-                            filter.iter().map(|f| format!("%{}%", f)).collect::<Vec<_>>()
-                        ))
+                        #doc_attributes
+                        #( #field_attributes )*
+                    },
+                    rocket_attributes,
+                    filter_field.clone(),
+                    quote!(Option<Vec<#ty>>),
+                ));
+                writes.push(quote! { writer.multiple(#filter_param, &self.#filter_field); });
+                reads.push(quote! {
+                    #filter_param => reader.multiple(#filter_param, #expected, &value, #options, #parser, &mut filters.#filter_field),
+                });
+
+                match (filter_attr.insensitive, filter_attr.substring) {
+                    (false, false) => {
+                        quote! { #column.eq(any(filter)) }
+                    }
+                    (false, true) => {
+                        quote! {
+                            #column.like(any(
+                                filter.iter().map(|f| format!("%{}%", f)).collect::<Vec<_>>()
+                            ))
+                        }
+                    }
+                    (true, false) => {
+                        quote! { #column.ilike(any(filter)) }
+                    }
+                    (true, true) => {
+                        quote! {
+                            #column.ilike(any(
+                                filter.iter().map(|f| format!("%{}%", f)).collect::<Vec<_>>()
+                            ))
+                        }
                     }
                 }
+            } else {
+                filter_fields.push((
+                    quote! {
+                        #doc_attributes
+                        #( #field_attributes )*
+                    },
+                    rocket_attributes,
+                    filter_field.clone(),
+                    quote!(Option<#ty>),
+                ));
+                writes.push(quote! { writer.single(#filter_param, &self.#filter_field); });
+                reads.push(quote! {
+                    #filter_param => reader.single(#filter_param, #expected, &value, #options, #parser, &mut filters.#filter_field),
+                });
+                match (filter_attr.insensitive, filter_attr.substring) {
+                    (false, false) => {
+                        quote! { #column.eq(filter) }
+                    }
+                    (false, true) => {
+                        quote! { #column.like(format!("%{}%", filter)) }
+                    }
+                    (true, false) => {
+                        quote! { #column.ilike(filter) }
+                    }
+                    (true, true) => {
+                        quote! { #column.ilike(format!("%{}%", filter)) }
+                    }
+                }
+            };
+
+            queries.push(quote! {
+                if let Some(filter) = filters.#filter_field {
+                    query = query.filter(#q);
+                }
+            });
+        }
+
+        // `{field}_contains`, a JSON document, and `{field}_has_key`, any of the keys
+        // with `has_any_key`, `multiple` by default
+        if let Some(jsonb) = &filter_attr.jsonb {
+            let column = quote!(#table_name::#field);
+
+            let contains_field = format_ident!("{}_contains", param);
+            let contains_param = format!("{param}_contains");
+            let contains_doc = "Matches the documents containing this JSON value, \
+                                e.g. `{\"address\":{\"city\":\"Paris\"}}`.";
+            filter_params.push((contains_field.clone(), contains_param.clone(), false));
+
+            // `max_len` applies to the JSON text, which is neither normalized nor lenient
+            #[allow(unused_mut)]
+            let mut field_attributes = vec![quote! { #[doc = #contains_doc] }];
+            #[allow(unused_mut)]
+            let mut rocket_attributes: Vec<proc_macro2::TokenStream> = vec![];
+            #[cfg(feature = "utoipa")]
+            {
+                field_attributes.push(quote! { #[param(value_type = Option<String>)] });
+                if let Some(max_len) = max_len {
+                    field_attributes.push(quote! { #[param(max_length = #max_len)] });
+                }
             }
-        } else {
-            fields.push(quote! {
-                pub #field: Option<#ty>,
+            #[cfg(feature = "schemars")]
+            {
+                field_attributes.push(quote! { #[schemars(with = "Option<String>")] });
+                if let Some(max_len) = max_len {
+                    field_attributes.push(quote! { #[schemars(length(max = #max_len))] });
+                }
+            }
+            #[cfg(feature = "rocket")]
+            if let Some(max_len) = max_len {
+                rocket_attributes.push(quote! {
+                    #[field(validate = with(
+                        |values| values.iter().all(|value| ::diesel_filter::options::within_len(value, #max_len)),
+                        ::diesel_filter::options::too_long(#max_len),
+                    ))]
+                });
+            }
+            #[cfg(feature = "async-graphql")]
+            if let Some(max_len) = max_len {
+                field_attributes.push(quote! {
+                    #[graphql(validator(custom = |value: &::diesel_filter::json::JsonValue| {
+                        if ::diesel_filter::options::within_len(value, #max_len) {
+                            Ok(())
+                        } else {
+                            Err(::diesel_filter::options::too_long(#max_len))
+                        }
+                    }))]
+                });
+            }
+            #[cfg(any(feature = "actix", feature = "axum"))]
+            {
+                let serde_as_path = format!(
+                    "::diesel_filter::options::Single<{}, {trim}, {empty_as_none}, false>",
+                    const_limit(max_len),
+                );
+                field_attributes.push(quote! {
+                    #[serde_as(as = #serde_as_path)]
+                    #[serde(default)]
+                });
+            }
+
+            filter_fields.push((
+                quote!(#( #field_attributes )*),
+                rocket_attributes,
+                contains_field.clone(),
+                quote!(Option<::diesel_filter::json::JsonValue>),
+            ));
+            writes.push(quote! { writer.single(#contains_param, &self.#contains_field); });
+            reads.push(quote! {
+                #contains_param => reader.single(
+                    #contains_param,
+                    "JsonValue",
+                    &value,
+                    ::diesel_filter::options::FieldOptions {
+                        max_len: #max_len_tokens,
+                        trim: #trim,
+                        empty_as_none: #empty_as_none,
+                        ..Default::default()
+                    },
+                    ::diesel_filter::options::Strict,
+                    &mut filters.#contains_field,
+                ),
             });
-            match (filter_attr.insensitive, filter_attr.substring) {
-                (false, false) => {
-                    quote! { #table_name::#field.eq(filter) }
+            queries.push(quote! {
+                if let Some(filter) = filters.#contains_field {
+                    query = query.filter(#column.contains(filter.0));
                 }
-                (false, true) => {
-                    quote! { #table_name::#field.like(format!("%{}%", filter)) }
+            });
+            checks.push(quote! {
+                if let Some(filter) = &self.#contains_field {
+                    #value
+                    if !::diesel_filter::json::contains(value, filter) {
+                        return false;
+                    }
                 }
-                (true, false) => {
-                    quote! { #table_name::#field.ilike(filter) }
+            });
+            descriptors.push(quote! {
+                ::diesel_filter::FilterField {
+                    name: #contains_param,
+                    value_type: "JsonValue",
+                    operators: &[::diesel_filter::FilterOperator::Contains],
+                    nullable: #nullable,
+                    max: None,
+                    max_len: #max_len_tokens,
+                    description: Some(#contains_doc),
+                },
+            });
+
+            // `max` limits the keys, when there are several
+            let any_key = jsonb
+                .clone()
+                .unwrap_or_default()
+                .has_any_key
+                .unwrap_or(multiple);
+            let max = max.filter(|_| any_key);
+            let max_key_tokens = option_tokens(max);
+            let has_key_field = format_ident!("{}_has_key", param);
+            let has_key_param = format!("{param}_has_key");
+            let has_key_doc = if any_key {
+                "Matches the documents having any of these top-level keys."
+            } else {
+                "Matches the documents having this top-level key."
+            };
+            filter_params.push((has_key_field.clone(), has_key_param.clone(), any_key));
+
+            let options = field_options(max);
+            if normalize.any() {
+                let normalize_func = normalize_func(any_key);
+                normalizations
+                    .push(quote! { #options.#normalize_func(&mut filters.#has_key_field); });
+            }
+            let (field_attributes, rocket_attributes) = attributes(&has_key_field, any_key, max);
+            let (has_key_ty, q, check) = if any_key {
+                writes.push(quote! { writer.multiple(#has_key_param, &self.#has_key_field); });
+                reads.push(quote! {
+                    #has_key_param => reader.multiple(#has_key_param, #expected, &value, #options, #parser, &mut filters.#has_key_field),
+                });
+                (
+                    quote!(Option<Vec<#ty>>),
+                    quote!(#column.has_any_key(filter)),
+                    quote!(
+                        filter
+                            .iter()
+                            .any(|key| ::diesel_filter::json::has_key(value, key))
+                    ),
+                )
+            } else {
+                writes.push(quote! { writer.single(#has_key_param, &self.#has_key_field); });
+                reads.push(quote! {
+                    #has_key_param => reader.single(#has_key_param, #expected, &value, #options, #parser, &mut filters.#has_key_field),
+                });
+                (
+                    quote!(Option<#ty>),
+                    quote!(#column.has_key(filter)),
+                    quote!(::diesel_filter::json::has_key(value, filter)),
+                )
+            };
+            filter_fields.push((
+                quote! {
+                    #[doc = #has_key_doc]
+                    #( #field_attributes )*
+                },
+                rocket_attributes,
+                has_key_field.clone(),
+                has_key_ty,
+            ));
+            queries.push(quote! {
+                if let Some(filter) = filters.#has_key_field {
+                    query = query.filter(#q);
                 }
-                (true, true) => {
-                    quote! { #table_name::#field.ilike(format!("%{}%", filter)) }
+            });
+            checks.push(quote! {
+                if let Some(filter) = &self.#has_key_field {
+                    #value
+                    if !(#check) {
+                        return false;
+                    }
                 }
+            });
+            let mut operators = vec![quote!(HasKey)];
+            if any_key {
+                operators.push(quote!(Multiple));
             }
-        };
+            descriptors.push(quote! {
+                ::diesel_filter::FilterField {
+                    name: #has_key_param,
+                    value_type: #expected,
+                    operators: &[#( ::diesel_filter::FilterOperator::#operators ),*],
+                    nullable: #nullable,
+                    max: #max_key_tokens,
+                    max_len: #max_len_tokens,
+                    description: Some(#has_key_doc),
+                },
+            });
+        }
 
-        queries.push(quote! {
-            if let Some(filter) = filters.#field {
-                query = query.filter(#q);
+        // `{field}_on`, `{field}_before`, `{field}_after` and `{field}_within`
+        if let Some(date) = &filter_attr.date {
+            let tz = match date.clone().unwrap_or_default().tz.or(rules.tz.clone()) {
+                Some(tz) => quote!(#tz),
+                None => quote!(::diesel_filter::chrono::Utc),
+            };
+            let column = quote!(#table_name::#field);
+            let date_filters = [
+                (
+                    "on",
+                    quote!(Day),
+                    quote!(On),
+                    "Matches the whole day, e.g. `2024-01-31`, `today` or `today-1d`.",
+                    quote!(let (start, end) = filter.bounds::<#ty, _>(&#tz);),
+                    quote!(#column.ge(start).and(#column.lt(end))),
+                    quote!(*value >= start && *value < end),
+                ),
+                (
+                    "before",
+                    quote!(Moment),
+                    quote!(Before),
+                    "Before a date, a date-time or e.g. `now-1h`, excluded.",
+                    quote!(let end = filter.upper::<#ty, _>(&#tz);),
+                    quote!(#column.lt(end)),
+                    quote!(*value < end),
+                ),
+                (
+                    "after",
+                    quote!(Moment),
+                    quote!(After),
+                    "After a date, a date-time or e.g. `now-1h`, included.",
+                    quote!(let start = filter.lower::<#ty, _>(&#tz);),
+                    quote!(#column.ge(start)),
+                    quote!(*value >= start),
+                ),
+                (
+                    "within",
+                    quote!(Period),
+                    quote!(Within),
+                    "Within the last hours, days or weeks until now, e.g. `7d`, \
+                     or a calendar period like `this_month`.",
+                    quote!(let (start, end) = filter.bounds::<#ty, _>(&#tz);),
+                    quote!(#column.ge(start).and(#column.lt(end))),
+                    quote!(*value >= start && *value < end),
+                ),
+            ];
+            for (suffix, date_type, operator, doc, bounds, q, check) in date_filters {
+                let date_field = format_ident!("{}_{}", param, suffix);
+                let date_param = format!("{param}_{suffix}");
+                let date_expected = date_type.to_string();
+                filter_params.push((date_field.clone(), date_param.clone(), false));
+
+                #[allow(unused_mut)]
+                let mut field_attributes = vec![quote! { #[doc = #doc] }];
+                #[cfg(feature = "utoipa")]
+                field_attributes.push(quote! { #[param(value_type = Option<String>)] });
+                #[cfg(feature = "schemars")]
+                field_attributes.push(quote! { #[schemars(with = "Option<String>")] });
+                #[cfg(any(feature = "actix", feature = "axum"))]
+                field_attributes.push(quote! {
+                    #[serde_as(as = "Option<::diesel_filter::serde_with::DisplayFromStr>")]
+                });
+
+                filter_fields.push((
+                    quote!(#( #field_attributes )*),
+                    vec![],
+                    date_field.clone(),
+                    quote!(Option<::diesel_filter::dates::#date_type>),
+                ));
+                writes.push(quote! { writer.single(#date_param, &self.#date_field); });
+                reads.push(quote! {
+                    #date_param => reader.single(
+                        #date_param,
+                        #date_expected,
+                        &value,
+                        ::diesel_filter::options::FieldOptions {
+                            trim: #trim,
+                            empty_as_none: #empty_as_none,
+                            ..Default::default()
+                        },
+                        ::diesel_filter::options::Strict,
+                        &mut filters.#date_field,
+                    ),
+                });
+                queries.push(quote! {
+                    if let Some(filter) = filters.#date_field {
+                        #bounds
+                        query = query.filter(#q);
+                    }
+                });
+                checks.push(quote! {
+                    if let Some(filter) = &self.#date_field {
+                        #bounds
+                        #value
+                        if !(#check) {
+                            return false;
+                        }
+                    }
+                });
+                descriptors.push(quote! {
+                    ::diesel_filter::FilterField {
+                        name: #date_param,
+                        value_type: #date_expected,
+                        operators: &[::diesel_filter::FilterOperator::#operator],
+                        nullable: #nullable,
+                        max: None,
+                        max_len: None,
+                        description: Some(#doc),
+                    },
+                });
             }
-        });
+        }
+
+        // `{field}_since` and `{field}_until`, parsed leniently as the lower and upper bounds
+        if filter_attr
+            .lenient
+            .is_some_and(|lenient| lenient.unwrap_or_default().range)
+        {
+            let column = quote!(#table_name::#field);
+            let range_filters = [
+                (
+                    "since",
+                    quote!(Lenient),
+                    quote!(Since),
+                    "Since a date-time, a date or e.g. `now-7d`, included.",
+                    quote!(#column.ge(filter)),
+                    quote!(value >= filter),
+                ),
+                (
+                    "until",
+                    quote!(LenientEnd),
+                    quote!(Until),
+                    "Until a date-time or e.g. `now-1h`, excluded, or the end of a date for date-times.",
+                    quote!(#column.lt(filter)),
+                    quote!(value < filter),
+                ),
+            ];
+            for (suffix, range_parser, operator, doc, q, check) in range_filters {
+                let range_field = format_ident!("{}_{}", param, suffix);
+                let range_param = format!("{param}_{suffix}");
+                filter_params.push((range_field.clone(), range_param.clone(), false));
+
+                #[allow(unused_mut)]
+                let mut field_attributes = vec![quote! { #[doc = #doc] }];
+                #[cfg(any(feature = "actix", feature = "axum"))]
+                {
+                    let serde_as_path = format!(
+                        "::diesel_filter::options::Single<{{ usize::MAX }}, {trim}, {empty_as_none}, false, ::diesel_filter::options::{range_parser}>",
+                    );
+                    field_attributes.push(quote! {
+                        #[serde_as(as = #serde_as_path)]
+                        #[serde(default)]
+                    });
+
+                    #[cfg(feature = "schemars")]
+                    {
+                        let schema_type = format!("Option<{expected}>");
+                        field_attributes.push(quote! { #[schemars(with = #schema_type)] });
+                    }
+                }
+
+                filter_fields.push((
+                    quote!(#( #field_attributes )*),
+                    vec![],
+                    range_field.clone(),
+                    quote!(Option<#ty>),
+                ));
+                writes.push(quote! { writer.single(#range_param, &self.#range_field); });
+                reads.push(quote! {
+                    #range_param => reader.single(
+                        #range_param,
+                        #expected,
+                        &value,
+                        ::diesel_filter::options::FieldOptions {
+                            trim: #trim,
+                            empty_as_none: #empty_as_none,
+                            ..Default::default()
+                        },
+                        ::diesel_filter::options::#range_parser,
+                        &mut filters.#range_field,
+                    ),
+                });
+                queries.push(quote! {
+                    if let Some(filter) = filters.#range_field {
+                        query = query.filter(#q);
+                    }
+                });
+                checks.push(quote! {
+                    if let Some(filter) = &self.#range_field {
+                        #value
+                        if !(#check) {
+                            return false;
+                        }
+                    }
+                });
+                descriptors.push(quote! {
+                    ::diesel_filter::FilterField {
+                        name: #range_param,
+                        value_type: #expected,
+                        operators: &[::diesel_filter::FilterOperator::#operator],
+                        nullable: #nullable,
+                        max: None,
+                        max_len: None,
+                        description: Some(#doc),
+                    },
+                });
+            }
+        }
     }
 
     if has_multiple {
         uses.push(quote! { use diesel::dsl::any; })
     }
 
+    // Whether the filters a rule is about are set, and their parameter names. An empty
+    // list doesn't narrow the query down, `= ANY('{}')` matching nothing and `@> '{}'`
+    // everything: it is not set as far as the rules are concerned.
+    let mut rule_fields = |paths: &PathList| {
+        let mut set = vec![];
+        let mut params = vec![];
+        for path in paths.iter() {
+            match filter_params
+                .iter()
+                .find(|(field, _, _)| path.is_ident(field))
+            {
+                Some((field, param, list)) => {
+                    set.push(if *list {
+                        quote!(self.#field.as_ref().is_some_and(|values| !values.is_empty()))
+                    } else {
+                        quote!(self.#field.is_some())
+                    });
+                    params.push(param.clone());
+                }
+                None => errors.push(
+                    darling::Error::custom("`filter_struct` rules must name filters of the struct")
+                        .with_span(path)
+                        .write_errors(),
+                ),
+            }
+        }
+        (set, params)
+    };
+
+    let mut rule_checks = vec![];
+    if let Some(require_any) = &rules.require_any {
+        let (set, params) = rule_fields(require_any);
+        rule_checks.push(quote! {
+            if #( !#set )&&* {
+                return Err(::diesel_filter::FilterRuleError::RequireAny {
+                    fields: vec![#( #params ),*],
+                });
+            }
+        });
+    }
+    for exclusive in &rules.exclusive {
+        let (set, params) = rule_fields(exclusive);
+        rule_checks.push(quote! {
+            let fields: Vec<&'static str> = [#( (#params, #set) ),*]
+                .into_iter()
+                .filter_map(|(param, set)| set.then_some(param))
+                .collect();
+            if fields.len() > 1 {
+                return Err(::diesel_filter::FilterRuleError::Exclusive { fields });
+            }
+        });
+    }
+
+    // With rules, the generated functions validate the filters and return a `Result`
+    let validated = |ty: proc_macro2::TokenStream| {
+        if rule_checks.is_empty() {
+            ty
+        } else {
+            quote!(Result<#ty, ::diesel_filter::FilterRuleError>)
+        }
+    };
+    let returned = |expr: proc_macro2::TokenStream| {
+        if rule_checks.is_empty() {
+            expr
+        } else {
+            quote!(Ok(#expr))
+        }
+    };
+    let (validate, try_) = if rule_checks.is_empty() {
+        (quote!(), quote!())
+    } else {
+        (quote!(filters.validate()?;), quote!(?))
+    };
+    let boxed_query = validated(quote!(#table_name::BoxedQuery<'a, diesel::pg::Pg>));
+    let count_query =
+        validated(quote!(#table_name::BoxedQuery<'a, diesel::pg::Pg, diesel::sql_types::BigInt>));
+    let exists_query = validated(quote! {
+        diesel::dsl::select<diesel::dsl::exists<#table_name::BoxedQuery<'a, diesel::pg::Pg>>>
+    });
+    let delete_query = validated(quote! {
+        diesel::query_builder::BoxedDeleteStatement<'a, diesel::pg::Pg, #table_name::table>
+    });
+    let update_query = validated(quote! {
+        diesel::query_builder::BoxedUpdateStatement<'a, diesel::pg::Pg, #table_name::table, C::Changeset>
+    });
+
+    #[allow(unused_mut)]
+    let mut struct_attributes: Vec<proc_macro2::TokenStream> = vec![];
     let mut extra_derive = vec![];
     extra_derive.push(quote!(Debug));
     extra_derive.push(quote!(Default));
@@ -220,46 +1539,327 @@ pub fn diesel_filter_derive(input: TokenStream) -> TokenStream {
     #[cfg(feature = "utoipa")]
     extra_derive.push(quote!(utoipa::IntoParams));
 
+    // Rocket has no hook to transform the values of a form, the normalized filters are
+    // parsed by a private struct deriving `FromForm` then normalized in `finalize`
+    #[allow(unused_mut)]
+    let mut rocket_form = quote!();
+    #[allow(unused_mut)]
+    let mut fields = vec![];
     #[cfg(feature = "rocket")]
-    let filters_struct = quote! {
-        #[derive(FromForm, #( #extra_derive, )*)]
-        pub struct #filter_struct_ident {
-            #( #fields )*
+    if normalizations.is_empty() {
+        extra_derive.push(quote!(FromForm));
+        for (attributes, rocket_attributes, field, ty) in &filter_fields {
+            fields.push(quote! {
+                #attributes
+                #( #rocket_attributes )*
+                pub #field: #ty,
+            });
         }
-    };
+    } else {
+        let field_names: Vec<_> = filter_fields.iter().map(|(_, _, field, _)| field).collect();
+        let form_fields = filter_fields
+            .iter()
+            .map(|(_, rocket_attributes, field, ty)| {
+                quote! {
+                    #( #rocket_attributes )*
+                    #field: #ty,
+                }
+            });
+        rocket_form = quote! {
+            const _: () = {
+                // Public for the `Context` of the filters, but out of reach in this block
+                #[derive(FromForm)]
+                pub struct Form {
+                    #( #form_fields )*
+                }
+
+                #[rocket::async_trait]
+                impl<'r> rocket::form::FromForm<'r> for #filter_struct_ident {
+                    type Context = <Form as rocket::form::FromForm<'r>>::Context;
+
+                    fn init(opts: rocket::form::Options) -> Self::Context {
+                        <Form as rocket::form::FromForm<'r>>::init(opts)
+                    }
+
+                    fn push_value(ctxt: &mut Self::Context, field: rocket::form::ValueField<'r>) {
+                        <Form as rocket::form::FromForm<'r>>::push_value(ctxt, field)
+                    }
+
+                    async fn push_data(ctxt: &mut Self::Context, field: rocket::form::DataField<'r, '_>) {
+                        <Form as rocket::form::FromForm<'r>>::push_data(ctxt, field).await
+                    }
+
+                    fn push_error(ctxt: &mut Self::Context, error: rocket::form::Error<'r>) {
+                        <Form as rocket::form::FromForm<'r>>::push_error(ctxt, error)
+                    }
+
+                    fn finalize(ctxt: Self::Context) -> rocket::form::Result<'r, Self> {
+                        let form = <Form as rocket::form::FromForm<'r>>::finalize(ctxt)?;
+                        let mut filters = Self {
+                            #( #field_names: form.#field_names, )*
+                        };
+                        #( #normalizations )*
+                        Ok(filters)
+                    }
+                }
+            };
+        };
+    }
+    if fields.is_empty() {
+        for (attributes, _, field, ty) in &filter_fields {
+            fields.push(quote! {
+                #attributes
+                pub #field: #ty,
+            });
+        }
+    }
+
+    #[cfg(feature = "async-graphql")]
+    extra_derive.push(quote!(async_graphql::InputObject));
+
+    #[cfg(feature = "schemars")]
+    extra_derive.push(quote!(schemars::JsonSchema));
 
     #[cfg(any(feature = "actix", feature = "axum"))]
-    let filters_struct = quote! {
-        #[::diesel_filter::serde_with::serde_as(crate = "::diesel_filter::serde_with")]
-        #[derive(serde::Deserialize, #( #extra_derive, )*)]
-        pub struct #filter_struct_ident {
-            #( #fields )*
+    {
+        // `multiple` fields have their own `schemars(with)`, see above
+        struct_attributes.push(
+            quote! { #[::diesel_filter::serde_with::serde_as(crate = "::diesel_filter::serde_with", schemars = false)] },
+        );
+        extra_derive.push(quote!(serde::Deserialize));
+        if rules.query_string {
+            extra_derive.push(quote!(serde::Serialize));
         }
-    };
+    }
 
-    #[cfg(not(any(feature = "rocket", feature = "actix", feature = "axum")))]
     let filters_struct = quote! {
+        #( #struct_attributes )*
         #[derive(#( #extra_derive, )*)]
         pub struct #filter_struct_ident {
             #( #fields )*
         }
+
+        #rocket_form
+    };
+
+    let query = returned(quote!(query));
+    let count = returned(quote!(Self::filter(#( #scope_args )* filters)#try_.count()));
+    let exists = returned(
+        quote!(diesel::select(diesel::dsl::exists(Self::filter(#( #scope_args )* filters)#try_))),
+    );
+    let predicates_bound = quote! {
+        Q: diesel::query_dsl::methods::FilterDsl<
+            ::diesel_filter::predicates::Predicate<'a, #table_name::table>,
+            Output = Q,
+        >
+    };
+
+    // Without scopes, `unscoped` would be the same as `filter`
+    let (apply_scope_func, apply_scope, unscoped_func) = if scope_queries.is_empty() {
+        (quote!(), quote!(), quote!())
+    } else {
+        let apply_scope_func = quote! {
+            /// Applies the `filter_scope` predicates to a boxed select, delete or update.
+            fn apply_scope<'a, Q>(#( #scope_params )* boxed: Q) -> Q
+            where
+                #predicates_bound,
+            {
+                let mut query = ::diesel_filter::predicates::Predicates::new();
+
+                #( #scope_queries )*
+
+                query.apply(boxed)
+            }
+        };
+        let apply_scope = quote! {
+            let query = Self::apply_scope(#( #scope_args )* query);
+        };
+        let unscoped_func = quote! {
+            /// Same as `filter` without the `filter_scope` predicates, e.g. for admin tools.
+            pub fn unscoped<'a>(filters: #filter_struct_ident) -> #boxed_query {
+                #validate
+                let query = Self::apply_filters(filters, #table_name::table.into_boxed());
+
+                #query
+            }
+        };
+        (apply_scope_func, apply_scope, unscoped_func)
     };
 
     let filter_func = quote! {
-        pub fn filter<'a>(filters: #filter_struct_ident) -> #table_name::BoxedQuery<'a, diesel::pg::Pg> {
+        /// Applies the filters to a boxed select, delete or update.
+        fn apply_filters<'a, Q>(filters: #filter_struct_ident, boxed: Q) -> Q
+        where
+            #predicates_bound,
+        {
             #( #uses )*
-            let mut query = #table_name::table.into_boxed();
+            let mut query = ::diesel_filter::predicates::Predicates::new();
 
             #( #queries )*
 
-            query
+            query.apply(boxed)
+        }
+
+        #apply_scope_func
+
+        pub fn filter<'a>(
+            #( #scope_params )*
+            filters: #filter_struct_ident,
+        ) -> #boxed_query {
+            #validate
+            let query = #table_name::table.into_boxed();
+            #apply_scope
+            let query = Self::apply_filters(filters, query);
+
+            #query
+        }
+
+        #unscoped_func
+
+        /// Counts the rows matching the filters, with e.g. `.get_result::<i64>(conn)`.
+        pub fn count_filtered<'a>(
+            #( #scope_params )*
+            filters: #filter_struct_ident,
+        ) -> #count_query {
+            #count
+        }
+
+        /// Whether some rows match the filters, with e.g. `.get_result::<bool>(conn)`.
+        pub fn exists_filtered<'a>(
+            #( #scope_params )*
+            filters: #filter_struct_ident,
+        ) -> #exists_query {
+            #exists
+        }
+
+        /// Deletes the rows matching the filters, with e.g. `.execute(conn)`.
+        pub fn delete_filtered<'a>(
+            #( #scope_params )*
+            filters: #filter_struct_ident,
+        ) -> #delete_query {
+            #validate
+            let query = diesel::delete(#table_name::table).into_boxed();
+            #apply_scope
+            let query = Self::apply_filters(filters, query);
+
+            #query
+        }
+
+        /// Updates the rows matching the filters with `changes`, with e.g. `.execute(conn)`.
+        pub fn update_filtered<'a, C>(
+            #( #scope_params )*
+            filters: #filter_struct_ident,
+            changes: C,
+        ) -> #update_query
+        where
+            C: diesel::AsChangeset<Target = #table_name::table>,
+        {
+            #validate
+            let query = diesel::update(#table_name::table).set(changes).into_boxed();
+            #apply_scope
+            let query = Self::apply_filters(filters, query);
+
+            #query
+        }
+    };
+
+    let query_string_funcs = rules.query_string.then(|| {
+        quote! {
+            /// Serializes the filters to a query string that the `actix` and `axum`
+            /// integrations parse back, `multiple` values are comma separated.
+            pub fn to_query_string(&self) -> String {
+                self.to_query_string_with(::diesel_filter::QueryStyle::CommaSeparated)
+            }
+
+            /// Serializes the filters to a query string using the given style.
+            pub fn to_query_string_with(&self, style: ::diesel_filter::QueryStyle<'_>) -> String {
+                let mut writer = ::diesel_filter::query::QueryWriter::new(style);
+
+                #( #writes )*
+
+                writer.finish()
+            }
+
+            /// Parses the filters from a query string, independently of any web framework.
+            ///
+            /// `multiple` values can either be comma separated or given as repeated keys,
+            /// unknown parameters are ignored.
+            pub fn from_query_str(query: &str) -> Result<Self, ::diesel_filter::FilterParseError> {
+                let mut filters = Self::default();
+                let mut reader = ::diesel_filter::query::QueryReader::default();
+
+                for (key, value) in ::diesel_filter::query::parse(query) {
+                    match key.as_ref() {
+                        #( #reads )*
+                        _ => {}
+                    }
+                }
+
+                reader.finish(filters)
+            }
+        }
+    });
+    let from_str_impl = rules.query_string.then(|| {
+        quote! {
+            impl ::std::str::FromStr for #filter_struct_ident {
+                type Err = ::diesel_filter::FilterParseError;
+
+                fn from_str(query: &str) -> Result<Self, Self::Err> {
+                    Self::from_query_str(query)
+                }
+            }
+
+            impl ::diesel_filter::FromQueryStr for #filter_struct_ident {
+                fn from_query_str(query: &str) -> Result<Self, ::diesel_filter::FilterParseError> {
+                    Self::from_query_str(query)
+                }
+            }
+        }
+    });
+
+    let matches_func = rules.matches.then(|| {
+        quote! {
+            /// Whether the model would be returned by `filter`, without querying the database,
+            /// e.g. to check if an updated row still matches.
+            pub fn matches(&self, #( #matches_params )* model: &#struct_ident) -> bool {
+                #( #scope_checks )*
+                #( #checks )*
+
+                true
+            }
+        }
+    });
+
+    let query_funcs = quote! {
+        /// The filters of this struct, e.g. to build filter forms dynamically.
+        pub const FIELDS: &'static [::diesel_filter::FilterField] = &[
+            #( #descriptors )*
+        ];
+
+        #query_string_funcs
+
+        #( #graphql_normalizations )*
+
+        /// Checks the `filter_struct` rules, which `filter` does before building the query.
+        pub fn validate(&self) -> Result<(), ::diesel_filter::FilterRuleError> {
+            #( #rule_checks )*
+
+            Ok(())
         }
+
+        #matches_func
     };
 
     if errors.is_empty() {
         quote! {
             #filters_struct
 
+            impl #filter_struct_ident {
+                #query_funcs
+            }
+
+            #from_str_impl
+
             impl #struct_ident {
                 #filter_func
             }
@@ -0,0 +1,244 @@
+use crate::{Paginated, PaginatedQuery};
+use diesel::{prelude::*, query_builder::*, query_dsl::methods::LoadQuery, sql_types::BigInt};
+
+/// A paginated query counting the total in a second statement,
+/// see `PaginatedQuery::count_separately`.
+///
+/// `C` is the query the count is made on, `NoCount` when it is made on the paginated
+/// query itself.
+#[derive(Debug, Clone, Copy)]
+pub struct SeparateCountQuery<T, C = NoCount> {
+    query: T,
+    count_source: C,
+    page: i64,
+    offset: i64,
+    per_page: i64,
+}
+
+/// The count source of `count_separately`, the paginated query itself.
+///
+/// It is not a query: `load_and_count_async` needs the count source of
+/// `count_separately_with`.
+#[derive(Debug, Clone, Copy)]
+pub struct NoCount;
+
+/// The query counted by `SeparateCountQuery::load_and_count`, borrowed from the
+/// paginated query with `NoCount`.
+pub trait CountSource<'a, T: 'a> {
+    type Source: 'a;
+
+    fn count_source(&'a self, query: &'a T) -> Self::Source;
+}
+
+impl<'a, T: 'a> CountSource<'a, T> for NoCount {
+    type Source = &'a T;
+
+    fn count_source(&'a self, query: &'a T) -> Self::Source {
+        query
+    }
+}
+
+impl<'a, T: 'a, C: Query + 'a> CountSource<'a, T> for C {
+    type Source = &'a C;
+
+    fn count_source(&'a self, _query: &'a T) -> Self::Source {
+        self
+    }
+}
+
+impl<T> PaginatedQuery<T> {
+    /// Runs the count as a separate `SELECT COUNT(*) FROM (...)` statement
+    /// instead of wrapping the query in `SELECT *, COUNT(*) OVER ()`.
+    ///
+    /// The query keeps its own select list and is limited directly, which works with
+    /// joins selecting several columns with the same name, `DISTINCT ON` and `GROUP BY`.
+    /// The query must not have its own `LIMIT` or `OFFSET`.
+    ///
+    /// The count runs the query as is, including its `ORDER BY` which can't be removed
+    /// from a built query, `count_separately_with` counts a query given without it.
+    pub fn count_separately(self) -> SeparateCountQuery<T> {
+        let Self {
+            query,
+            page,
+            offset,
+            per_page,
+        } = self;
+
+        SeparateCountQuery {
+            query,
+            count_source: NoCount,
+            page,
+            offset,
+            per_page,
+        }
+    }
+
+    /// Same as `count_separately`, counting the rows of another instance of the query,
+    /// e.g. without its `ORDER BY`.
+    ///
+    /// Boxed queries cannot be cloned, and `diesel-async` needs to own both statements:
+    ///
+    /// ```ignore
+    /// Project::filter(filters.clone())
+    ///     .order(projects::name)
+    ///     .paginate(params)
+    ///     .count_separately_with(Project::filter(filters))
+    ///     .load_and_count_async::<Project, _>(&mut conn)
+    ///     .await
+    /// ```
+    pub fn count_separately_with<C>(self, count_source: C) -> SeparateCountQuery<T, C> {
+        let Self {
+            query,
+            page,
+            offset,
+            per_page,
+        } = self;
+
+        SeparateCountQuery {
+            query,
+            count_source,
+            page,
+            offset,
+            per_page,
+        }
+    }
+}
+
+impl<T, C> SeparateCountQuery<T, C> {
+    /// Loads the page, then counts the rows of the query, or of the one given to
+    /// `count_separately_with`.
+    pub fn load_and_count<'a, U, Conn>(&'a self, conn: &mut Conn) -> QueryResult<Paginated<U>>
+    where
+        C: CountSource<'a, T>,
+        LimitedQuery<&'a T>: LoadQuery<'a, Conn, U>,
+        CountQuery<C::Source>: LoadQuery<'a, Conn, i64>,
+        Conn: diesel::connection::Connection,
+    {
+        let items = LimitedQuery {
+            query: &self.query,
+            per_page: self.per_page,
+            offset: self.offset,
+        }
+        .load::<U>(conn)?;
+        let count_query = CountQuery {
+            query: self.count_source.count_source(&self.query),
+        };
+        let num_total = count_query.get_result::<i64>(conn)?;
+
+        Ok(Paginated {
+            page: self.page,
+            per_page: self.per_page,
+            items,
+            num_total,
+        })
+    }
+
+    /// Same as `load_and_count`, for `diesel-async` connections, counting the rows of
+    /// the query given to `count_separately_with`.
+    #[cfg(feature = "diesel-async")]
+    pub async fn load_and_count_async<'a, U, Conn>(
+        self,
+        conn: &mut Conn,
+    ) -> QueryResult<Paginated<U>>
+    where
+        LimitedQuery<T>: diesel_async::methods::LoadQuery<'a, Conn, U> + 'a,
+        CountQuery<C>: diesel_async::methods::LoadQuery<'a, Conn, i64> + 'a,
+        Conn: diesel_async::AsyncConnection,
+        U: Send,
+    {
+        use diesel_async::RunQueryDsl;
+
+        let Self {
+            query,
+            count_source,
+            page,
+            offset,
+            per_page,
+        } = self;
+
+        let items_query = LimitedQuery {
+            query,
+            per_page,
+            offset,
+        };
+        let items = RunQueryDsl::load::<U>(items_query, conn).await?;
+        let count_query = CountQuery {
+            query: count_source,
+        };
+        let num_total = RunQueryDsl::get_result::<i64>(count_query, conn).await?;
+
+        Ok(Paginated {
+            page,
+            per_page,
+            items,
+            num_total,
+        })
+    }
+}
+
+/// The query with `LIMIT` and `OFFSET` appended, loading the items of a `SeparateCountQuery`.
+#[derive(Debug, Clone, Copy)]
+pub struct LimitedQuery<T> {
+    query: T,
+    per_page: i64,
+    offset: i64,
+}
+
+impl<T: Query> Query for LimitedQuery<T> {
+    type SqlType = T::SqlType;
+}
+
+impl<T> QueryId for LimitedQuery<T> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T, Conn> RunQueryDsl<Conn> for LimitedQuery<T> where Conn: diesel::connection::Connection {}
+
+impl<T, DB> QueryFragment<DB> for LimitedQuery<T>
+where
+    T: QueryFragment<DB>,
+    DB: diesel::backend::Backend,
+    i64: diesel::serialize::ToSql<BigInt, DB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        self.query.walk_ast(out.reborrow())?;
+        out.push_sql(" LIMIT ");
+        out.push_bind_param::<BigInt, _>(&self.per_page)?;
+        out.push_sql(" OFFSET ");
+        out.push_bind_param::<BigInt, _>(&self.offset)?;
+        Ok(())
+    }
+}
+
+/// `SELECT COUNT(*)` over the query, loading the total of a `SeparateCountQuery`.
+#[derive(Debug, Clone, Copy)]
+pub struct CountQuery<T> {
+    query: T,
+}
+
+impl<T> Query for CountQuery<T> {
+    type SqlType = BigInt;
+}
+
+impl<T> QueryId for CountQuery<T> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T, Conn> RunQueryDsl<Conn> for CountQuery<T> where Conn: diesel::connection::Connection {}
+
+impl<T, DB> QueryFragment<DB> for CountQuery<T>
+where
+    T: QueryFragment<DB>,
+    DB: diesel::backend::Backend,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        out.push_sql("SELECT COUNT(*) FROM (");
+        self.query.walk_ast(out.reborrow())?;
+        out.push_sql(") t");
+        Ok(())
+    }
+}
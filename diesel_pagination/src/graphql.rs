@@ -0,0 +1,232 @@
+use crate::{GetPaginationParams, Keyset, KeysetDsl, PaginationParams};
+use async_graphql::{OutputType, connection::EmptyFields};
+use diesel::{
+    Column, connection::Connection as DieselConnection, prelude::*, query_dsl::methods::LoadQuery,
+};
+use std::{fmt::Display, str::FromStr};
+
+pub use async_graphql::connection::PageInfo;
+
+/// A Relay connection, the cursors are the values of the key the rows are paginated on.
+pub type Connection<T> = async_graphql::connection::Connection<String, T>;
+
+/// An edge of a `Connection`.
+pub type Edge<T> = async_graphql::connection::Edge<String, T, EmptyFields>;
+
+/// The Relay pagination arguments, usually taken from the arguments of a resolver.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionArgs {
+    pub first: Option<i32>,
+    pub after: Option<String>,
+    pub last: Option<i32>,
+    pub before: Option<String>,
+}
+
+pub trait PaginateConnection: Sized {
+    /// Loads the page of a Relay connection described by `args`, with keyset pagination on
+    /// `key`, which must be unique and part of the selected columns.
+    ///
+    /// Without `first` or `last`, the first `PaginationParams::DEFAULT_PER_PAGE` rows are
+    /// returned, or fewer with a smaller maximum page size. `first` and `last` can't be more
+    /// than `ConnectionQuery::MAX_PAGE_SIZE`, see `ConnectionQuery::max_page_size`.
+    ///
+    /// One more row is loaded to compute `has_next_page` (or `has_previous_page` with
+    /// `last`). The other one is a guess: `has_previous_page` is set when `after` is given
+    /// and `has_next_page` when `before` is given, as the row of the cursor most probably
+    /// still exists. Rows are not looked up on the other side of the cursor.
+    ///
+    /// ```ignore
+    /// async fn projects(
+    ///     &self,
+    ///     ctx: &Context<'_>,
+    ///     filters: ProjectFilters,
+    ///     first: Option<i32>,
+    ///     after: Option<String>,
+    ///     last: Option<i32>,
+    ///     before: Option<String>,
+    /// ) -> async_graphql::Result<Connection<Project>> {
+    ///     let args = ConnectionArgs { first, after, last, before };
+    ///     Project::filter(filters)
+    ///         .paginate_connection(projects::id, args)
+    ///         .load_connection_async(&mut conn, |project: &Project| project.id)
+    ///         .await
+    /// }
+    /// ```
+    fn paginate_connection<K: Column>(
+        self,
+        key: K,
+        args: ConnectionArgs,
+    ) -> ConnectionQuery<Self, K>;
+}
+
+impl<T> PaginateConnection for T {
+    fn paginate_connection<K: Column>(
+        self,
+        key: K,
+        args: ConnectionArgs,
+    ) -> ConnectionQuery<Self, K> {
+        ConnectionQuery {
+            query: self,
+            key,
+            args,
+            max_page_size: ConnectionQuery::<Self, K>::MAX_PAGE_SIZE,
+        }
+    }
+}
+
+/// A query paginated as a Relay connection, see `PaginateConnection::paginate_connection`.
+#[derive(Debug, Clone)]
+pub struct ConnectionQuery<Q, K> {
+    query: Q,
+    key: K,
+    args: ConnectionArgs,
+    max_page_size: i32,
+}
+
+impl<Q, K> ConnectionQuery<Q, K> {
+    /// Default maximum of `first` and `last`.
+    pub const MAX_PAGE_SIZE: i32 = 100;
+
+    /// Sets the maximum of `first` and `last`, larger values are rejected. It also caps
+    /// the default page size.
+    pub fn max_page_size(mut self, max_page_size: i32) -> Self {
+        self.max_page_size = max_page_size;
+        self
+    }
+
+    pub fn load_connection<'q, U, V, F, Conn>(
+        self,
+        conn: &mut Conn,
+        key_of: F,
+    ) -> async_graphql::Result<Connection<U>>
+    where
+        U: OutputType,
+        V: FromStr + Display,
+        V::Err: Display,
+        F: FnMut(&U) -> V,
+        Conn: DieselConnection,
+        Q: KeysetDsl<K, V> + RunQueryDsl<Conn> + LoadQuery<'q, Conn, U>,
+    {
+        let window = Window::new(self.args, self.max_page_size)?;
+        let rows = window.query(self.query, self.key)?.load::<U>(conn)?;
+        Ok(window.connection(rows, key_of))
+    }
+
+    /// Same as `load_connection`, for `diesel-async` connections.
+    ///
+    /// The returned future is `Send`, as required by `async-graphql` resolvers.
+    #[cfg(feature = "diesel-async")]
+    pub fn load_connection_async<'q, U, V, F, Conn>(
+        self,
+        conn: &'q mut Conn,
+        key_of: F,
+    ) -> impl Future<Output = async_graphql::Result<Connection<U>>> + Send + 'q
+    where
+        U: OutputType + Send + 'q,
+        V: FromStr + Display,
+        V::Err: Display,
+        F: FnMut(&U) -> V + Send + 'q,
+        Conn: diesel_async::AsyncConnection,
+        Q: KeysetDsl<K, V> + diesel_async::methods::LoadQuery<'q, Conn, U> + 'q,
+    {
+        // The query is moved into diesel-async's boxed future out of the async block,
+        // whose `Send` bound would otherwise not be provable for borrowed boxed queries
+        let load = Window::new(self.args, self.max_page_size).and_then(|window| {
+            let query = window.query(self.query, self.key)?;
+            Ok((diesel_async::RunQueryDsl::load::<U>(query, conn), window))
+        });
+        async move {
+            let (load, window) = load?;
+            let rows = load.await?;
+            Ok(window.connection(rows, key_of))
+        }
+    }
+}
+
+/// The rows to fetch for some `ConnectionArgs`.
+struct Window {
+    after: Option<String>,
+    before: Option<String>,
+    size: i64,
+    backward: bool,
+}
+
+impl Window {
+    fn new(args: ConnectionArgs, max_page_size: i32) -> async_graphql::Result<Self> {
+        let (size, backward) = match (args.first, args.last) {
+            (Some(_), Some(_)) => {
+                return Err("`first` and `last` can't be used together".into());
+            }
+            (Some(n), None) | (None, Some(n)) if n < 0 => {
+                return Err("`first` and `last` must be positive".into());
+            }
+            (Some(n), None) | (None, Some(n)) if n > max_page_size => {
+                return Err(
+                    format!("`first` and `last` can't be more than {max_page_size}").into(),
+                );
+            }
+            (Some(first), None) => (first.into(), false),
+            (None, Some(last)) => (last.into(), true),
+            (None, None) => (
+                PaginationParams::DEFAULT_PER_PAGE.min(max_page_size.into()),
+                false,
+            ),
+        };
+        Ok(Self {
+            after: args.after,
+            before: args.before,
+            size,
+            backward,
+        })
+    }
+
+    fn query<Q, K, V>(&self, query: Q, key: K) -> async_graphql::Result<Q>
+    where
+        Q: KeysetDsl<K, V>,
+        V: FromStr,
+        V::Err: Display,
+    {
+        let decode = |cursor: &Option<String>| {
+            cursor
+                .as_deref()
+                .map(str::parse::<V>)
+                .transpose()
+                .map_err(|err| format!("invalid cursor: {err}"))
+        };
+        Ok(query.keyset(Keyset {
+            key,
+            after: decode(&self.after)?,
+            before: decode(&self.before)?,
+            descending: self.backward,
+            // One more row tells if there is a page after this one
+            per_page: self.size + 1,
+        }))
+    }
+
+    fn connection<U, V, F>(&self, mut rows: Vec<U>, mut key_of: F) -> Connection<U>
+    where
+        U: OutputType,
+        V: Display,
+        F: FnMut(&U) -> V,
+    {
+        let has_more = rows.len() as i64 > self.size;
+        rows.truncate(self.size as usize);
+        if self.backward {
+            rows.reverse();
+        }
+
+        // Rows before `after` (or after `before`) are not looked up, their cursor
+        // was given by the client so they most probably exist: this is a guess.
+        let (has_previous_page, has_next_page) = if self.backward {
+            (has_more, self.before.is_some())
+        } else {
+            (self.after.is_some(), has_more)
+        };
+        let mut connection = Connection::new(has_previous_page, has_next_page);
+        connection.edges = rows
+            .into_iter()
+            .map(|row| Edge::new(key_of(&row).to_string(), row))
+            .collect();
+        connection
+    }
+}
@@ -0,0 +1,223 @@
+use diesel::{
+    Column,
+    connection::Connection,
+    dsl,
+    expression::{AsExpression, Expression},
+    prelude::*,
+    query_dsl::methods::{FilterDsl, LimitDsl, LoadQuery, OrderDsl},
+    sql_types::SingleValue,
+};
+use std::marker::PhantomData;
+
+/// Walks every row of a query in pages, see `PaginateAll::paginate_all`.
+#[derive(Debug, Clone, Copy)]
+pub struct AllPages<Q, K> {
+    query: Q,
+    key: K,
+    per_page: i64,
+}
+
+pub trait PaginateAll: Sized {
+    /// Walks every row of the query in pages of `per_page` rows, for exports and batch jobs.
+    ///
+    /// Pages are fetched with keyset pagination on `key`, which must be unique and part of
+    /// the selected columns: rows inserted or deleted while walking do not make the following
+    /// pages skip or repeat rows, as it would happen with offsets.
+    ///
+    /// The query is filtered and ordered on the key for every page, it has to be built again
+    /// each time: `paginate_all` is called on a closure returning a boxed query.
+    ///
+    /// ```ignore
+    /// for page in (|| Project::filter(filters.clone()))
+    ///     .paginate_all(projects::id, 500)
+    ///     .load_iter(&mut conn, |project: &Project| project.id)
+    /// {
+    ///     export(page?)?;
+    /// }
+    /// ```
+    fn paginate_all<K: Column>(self, key: K, per_page: i64) -> AllPages<Self, K>;
+}
+
+impl<T> PaginateAll for T {
+    fn paginate_all<K: Column>(self, key: K, per_page: i64) -> AllPages<Self, K> {
+        AllPages {
+            query: self,
+            key,
+            per_page,
+        }
+    }
+}
+
+impl<Q, K: Copy> AllPages<Q, K> {
+    /// The query of the page after the row with the key `after`.
+    fn page<T, V>(&mut self, after: Option<V>) -> T
+    where
+        Q: FnMut() -> T,
+        T: KeysetDsl<K, V>,
+    {
+        (self.query)().keyset(Keyset {
+            key: self.key,
+            after,
+            before: None,
+            descending: false,
+            per_page: self.per_page,
+        })
+    }
+
+    /// Iterates over the pages, `key_of` returns the value of the key for a row.
+    ///
+    /// The iteration stops after the first error.
+    pub fn load_iter<U, V, F, Conn>(
+        self,
+        conn: &mut Conn,
+        key_of: F,
+    ) -> PagesIter<'_, Q, K, V, F, Conn, U>
+    where
+        F: FnMut(&U) -> V,
+    {
+        PagesIter {
+            pages: self,
+            conn,
+            key_of,
+            after: None,
+            done: false,
+            _row: PhantomData,
+        }
+    }
+
+    /// Same as `load_iter`, for `diesel-async` connections.
+    #[cfg(feature = "diesel-async")]
+    pub fn load_stream<'c, T, U, V, F, Conn>(
+        self,
+        conn: &'c mut Conn,
+        key_of: F,
+    ) -> impl futures_util::Stream<Item = QueryResult<Vec<U>>> + 'c
+    where
+        Q: FnMut() -> T + 'c,
+        K: 'c,
+        V: Clone + 'c,
+        F: FnMut(&U) -> V + 'c,
+        U: Send + 'c,
+        Conn: diesel_async::AsyncConnection,
+        T: KeysetDsl<K, V> + diesel_async::methods::LoadQuery<'c, Conn, U> + 'c,
+    {
+        let state = (self, conn, key_of, None::<V>, false);
+        futures_util::stream::unfold(
+            state,
+            |(mut pages, conn, mut key_of, after, done)| async move {
+                if done {
+                    return None;
+                }
+                let query = pages.page(after.clone());
+                match diesel_async::RunQueryDsl::load::<U>(query, conn).await {
+                    Ok(rows) if rows.is_empty() => None,
+                    Ok(rows) => {
+                        let done = (rows.len() as i64) < pages.per_page;
+                        let after = rows.last().map(&mut key_of).or(after);
+                        Some((Ok(rows), (pages, conn, key_of, after, done)))
+                    }
+                    Err(err) => Some((Err(err), (pages, conn, key_of, after, true))),
+                }
+            },
+        )
+    }
+}
+
+/// Iterator over the pages of `AllPages::load_iter`.
+pub struct PagesIter<'c, Q, K, V, F, Conn, U> {
+    pages: AllPages<Q, K>,
+    conn: &'c mut Conn,
+    key_of: F,
+    after: Option<V>,
+    done: bool,
+    _row: PhantomData<fn() -> U>,
+}
+
+impl<Q, T, K, V, F, Conn, U> Iterator for PagesIter<'_, Q, K, V, F, Conn, U>
+where
+    Q: FnMut() -> T,
+    K: Copy,
+    V: Clone,
+    F: FnMut(&U) -> V,
+    Conn: Connection,
+    T: KeysetDsl<K, V> + RunQueryDsl<Conn> + for<'q> LoadQuery<'q, Conn, U>,
+{
+    type Item = QueryResult<Vec<U>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let query = self.pages.page(self.after.clone());
+
+        match query.load::<U>(self.conn) {
+            Ok(rows) => {
+                self.done = (rows.len() as i64) < self.pages.per_page;
+                if let Some(last) = rows.last() {
+                    self.after = Some((self.key_of)(last));
+                }
+                (!rows.is_empty()).then_some(Ok(rows))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// A page of rows ordered by the key, between `after` and `before` when they are set.
+#[derive(Debug, Clone, Copy)]
+pub struct Keyset<K, V> {
+    pub(crate) key: K,
+    pub(crate) after: Option<V>,
+    pub(crate) before: Option<V>,
+    pub(crate) descending: bool,
+    pub(crate) per_page: i64,
+}
+
+/// Filters, orders and limits a query on its key for a `Keyset` page.
+///
+/// The query itself is filtered and ordered rather than a sub-select of it, so the key
+/// is not ambiguous when joins select several columns with the same name.
+/// It is implemented for the queries keeping their type when filtered, ordered and
+/// limited, such as boxed queries. The ordering of the query is replaced.
+pub trait KeysetDsl<K, V> {
+    fn keyset(self, keyset: Keyset<K, V>) -> Self;
+}
+
+impl<Q, K, V> KeysetDsl<K, V> for Q
+where
+    K: Expression + Copy,
+    K::SqlType: SingleValue,
+    V: AsExpression<K::SqlType>,
+    Q: FilterDsl<dsl::Gt<K, V>, Output = Q>
+        + FilterDsl<dsl::Lt<K, V>, Output = Q>
+        + OrderDsl<K, Output = Q>
+        + OrderDsl<dsl::Desc<K>, Output = Q>
+        + LimitDsl<Output = Q>,
+{
+    fn keyset(self, keyset: Keyset<K, V>) -> Self {
+        let Keyset {
+            key,
+            after,
+            before,
+            descending,
+            per_page,
+        } = keyset;
+
+        let mut query = self;
+        if let Some(after) = after {
+            query = FilterDsl::filter(query, key.gt(after));
+        }
+        if let Some(before) = before {
+            query = FilterDsl::filter(query, key.lt(before));
+        }
+        query = if descending {
+            OrderDsl::order(query, key.desc())
+        } else {
+            OrderDsl::order(query, key)
+        };
+        LimitDsl::limit(query, per_page)
+    }
+}
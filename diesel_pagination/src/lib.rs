@@ -1,5 +1,35 @@
 use diesel::{prelude::*, query_builder::*, sql_types::BigInt};
 
+mod count;
+#[cfg(feature = "async-graphql")]
+mod graphql;
+mod keyset;
+mod memory;
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+mod response;
+#[cfg(feature = "actix")]
+mod with_actix;
+#[cfg(feature = "aide")]
+mod with_aide;
+#[cfg(feature = "axum")]
+mod with_axum;
+#[cfg(feature = "okapi")]
+mod with_okapi;
+#[cfg(feature = "rocket")]
+mod with_rocket;
+
+pub use count::{CountQuery, CountSource, LimitedQuery, NoCount, SeparateCountQuery};
+#[cfg(feature = "async-graphql")]
+pub use graphql::{
+    Connection, ConnectionArgs, ConnectionQuery, Edge, PageInfo, PaginateConnection,
+};
+pub use keyset::{AllPages, Keyset, KeysetDsl, PagesIter, PaginateAll};
+pub use memory::PageItems;
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+pub use response::{BodyStyle, PaginatedResponse, TOTAL_COUNT_HEADER};
+#[cfg(feature = "axum")]
+pub use with_axum::pagination_links;
+
 /// This trait has to be implemented for a type to be passed into
 /// `Paginate::paginate`.
 ///
@@ -16,8 +46,16 @@ pub trait GetPaginationParams {
 #[derive(Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "rocket", derive(rocket::FromForm))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PaginationParams {
+    /// Number of items per page
+    #[cfg_attr(feature = "rocket", field(validate = with_rocket::at_least_one()))]
+    #[cfg_attr(feature = "schemars", schemars(range(min = 1)))]
     pub per_page: Option<i64>,
+    /// Page number, starting at 1
+    #[cfg_attr(feature = "rocket", field(validate = with_rocket::at_least_one()))]
+    #[cfg_attr(feature = "schemars", schemars(range(min = 1)))]
     pub page: Option<i64>,
 }
 
@@ -47,9 +85,15 @@ impl GetPaginationParams for PaginationParams {
     }
 }
 
+/// A page of items, along with the total number of items.
+///
+/// With the `rocket`, `actix` and `axum` features, it can be returned from a handler,
+/// see `PaginatedResponse`. With `axum`, the `Link` header is only sent when the
+/// `pagination_links` middleware is added to the router.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Paginated<T> {
     pub page: i64,
     pub per_page: i64,
@@ -84,7 +128,6 @@ pub struct PaginatedQuery<T> {
 }
 
 impl<T> PaginatedQuery<T> {
-    #[cfg(not(feature = "diesel-async"))]
     pub fn load_and_count<'a, U, Conn>(self, conn: &mut Conn) -> QueryResult<Paginated<U>>
     where
         Self: diesel::query_dsl::methods::LoadQuery<'a, Conn, (U, i64)>,
@@ -102,8 +145,12 @@ impl<T> PaginatedQuery<T> {
         })
     }
 
+    /// Same as `load_and_count`, for `diesel-async` connections.
     #[cfg(feature = "diesel-async")]
-    pub async fn load_and_count<'a, U, Conn>(self, conn: &mut Conn) -> QueryResult<Paginated<U>>
+    pub async fn load_and_count_async<'a, U, Conn>(
+        self,
+        conn: &mut Conn,
+    ) -> QueryResult<Paginated<U>>
     where
         Self: diesel_async::methods::LoadQuery<'a, Conn, (U, i64)> + 'a,
         Conn: diesel_async::AsyncConnection,
@@ -113,7 +160,7 @@ impl<T> PaginatedQuery<T> {
 
         let Self { page, per_page, .. } = self;
         let results = <Self as RunQueryDsl<Conn>>::load::<(U, i64)>(self, conn).await?;
-        let num_total = results.get(0).map(|x| x.1).unwrap_or(0);
+        let num_total = results.as_slice().first().map(|x| x.1).unwrap_or(0);
         let items = results.into_iter().map(|x| x.0).collect();
         Ok(Paginated {
             page,
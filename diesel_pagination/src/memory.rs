@@ -0,0 +1,60 @@
+use crate::{Paginated, PaginatedQuery};
+
+/// Items paginated in memory by `PaginatedQuery::into_paginated`.
+///
+/// The items of a `Vec<T>` are moved, those of a borrowed `Vec<T>`, slice or array are cloned.
+pub trait PageItems<T> {
+    /// The items of the page, along with the total number of items.
+    fn page_items(self, skip: usize, take: usize) -> (Vec<T>, i64);
+}
+
+impl<T> PageItems<T> for Vec<T> {
+    fn page_items(self, skip: usize, take: usize) -> (Vec<T>, i64) {
+        let num_total = self.len() as i64;
+        (self.into_iter().skip(skip).take(take).collect(), num_total)
+    }
+}
+
+impl<T: Clone, S: AsRef<[T]> + ?Sized> PageItems<T> for &S {
+    fn page_items(self, skip: usize, take: usize) -> (Vec<T>, i64) {
+        let items = self.as_ref();
+        let page = items.iter().skip(skip).take(take).cloned().collect();
+        (page, items.len() as i64)
+    }
+}
+
+impl<Q> PaginatedQuery<Q> {
+    /// Paginates the items in memory, with the same math as the SQL queries.
+    ///
+    /// ```ignore
+    /// let projects: Vec<Project> = cache.iter().filter(|p| filters.matches(p)).cloned().collect();
+    /// let paginated = projects.paginate(pagination).into_paginated();
+    /// // or, cloning only the items of the page
+    /// let paginated = (&projects).paginate(pagination).into_paginated();
+    /// ```
+    ///
+    /// `num_total` is the number of items, even when the page is past the last one.
+    pub fn into_paginated<T>(self) -> Paginated<T>
+    where
+        Q: PageItems<T>,
+    {
+        let (skip, take) = (self.skip(), self.take());
+        let (items, num_total) = self.query.page_items(skip, take);
+
+        Paginated {
+            page: self.page,
+            per_page: self.per_page,
+            items,
+            num_total,
+        }
+    }
+
+    // Postgres rejects negative offsets and limits, they count as 0 here
+    fn skip(&self) -> usize {
+        self.offset.try_into().unwrap_or(0)
+    }
+
+    fn take(&self) -> usize {
+        self.per_page.try_into().unwrap_or(0)
+    }
+}
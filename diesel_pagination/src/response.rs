@@ -0,0 +1,101 @@
+use crate::Paginated;
+
+/// The `X-Total-Count` header holding `Paginated::num_total`.
+pub const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+pub(crate) const PAGE_HEADER: &str = "X-Page";
+pub(crate) const PER_PAGE_HEADER: &str = "X-Per-Page";
+
+/// What is written in the body when a page is sent as an HTTP response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyStyle {
+    /// The whole `Paginated<T>` object.
+    #[default]
+    Envelope,
+    /// A bare array of the items, the pagination is only given through headers.
+    Items,
+}
+
+/// A page sent as an HTTP response, along with `Link`, `X-Total-Count`,
+/// `X-Page` and `X-Per-Page` headers.
+///
+/// `Paginated<T>` can be sent directly, this type allows to choose the body style.
+///
+/// With `axum`, the `Link` header needs the request URI and is only added by the
+/// `pagination_links` middleware.
+#[derive(Debug)]
+pub struct PaginatedResponse<T> {
+    pub paginated: Paginated<T>,
+    pub body: BodyStyle,
+}
+
+impl<T> Paginated<T> {
+    pub fn respond_with(self, body: BodyStyle) -> PaginatedResponse<T> {
+        PaginatedResponse {
+            paginated: self,
+            body,
+        }
+    }
+}
+
+impl<T> From<Paginated<T>> for PaginatedResponse<T> {
+    fn from(paginated: Paginated<T>) -> Self {
+        paginated.respond_with(BodyStyle::Envelope)
+    }
+}
+
+/// The numbers needed to build the pagination headers.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PageInfo {
+    pub page: i64,
+    pub per_page: i64,
+    pub num_total: i64,
+}
+
+impl<T> From<&Paginated<T>> for PageInfo {
+    fn from(paginated: &Paginated<T>) -> Self {
+        Self {
+            page: paginated.page,
+            per_page: paginated.per_page,
+            num_total: paginated.num_total,
+        }
+    }
+}
+
+impl PageInfo {
+    fn last_page(&self) -> i64 {
+        let per_page = self.per_page.max(1);
+        ((self.num_total + per_page - 1) / per_page).max(1)
+    }
+
+    /// Builds an RFC 8288 `Link` header pointing to the first, previous, next and last pages,
+    /// the other parameters of the request query are kept as is.
+    pub fn link_header(&self, path: &str, query: Option<&str>) -> String {
+        let params = query
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| {
+                let key = pair.split('=').next().unwrap_or_default();
+                !pair.is_empty() && key != "page" && key != "per_page"
+            })
+            .collect::<Vec<_>>();
+        let link = |page: i64, rel: &str| {
+            let mut query = params.clone();
+            let page = format!("page={page}");
+            let per_page = format!("per_page={}", self.per_page);
+            query.push(&page);
+            query.push(&per_page);
+            format!("<{path}?{}>; rel=\"{rel}\"", query.join("&"))
+        };
+
+        let last = self.last_page();
+        let mut links = vec![link(1, "first")];
+        if self.page > 1 {
+            links.push(link((self.page - 1).min(last), "prev"));
+        }
+        if self.page < last {
+            links.push(link(self.page + 1, "next"));
+        }
+        links.push(link(last, "last"));
+        links.join(", ")
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{
+    Paginated,
+    response::{
+        BodyStyle, PAGE_HEADER, PER_PAGE_HEADER, PageInfo, PaginatedResponse, TOTAL_COUNT_HEADER,
+    },
+};
+use actix_web::{HttpRequest, HttpResponse, Responder, body::BoxBody, http::header::LINK};
+use serde::Serialize;
+
+impl<T: Serialize> Responder for PaginatedResponse<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let info = PageInfo::from(&self.paginated);
+        let mut response = HttpResponse::Ok();
+        response
+            .insert_header((LINK, info.link_header(req.path(), Some(req.query_string()))))
+            .insert_header((TOTAL_COUNT_HEADER, info.num_total))
+            .insert_header((PAGE_HEADER, info.page))
+            .insert_header((PER_PAGE_HEADER, info.per_page));
+
+        match self.body {
+            BodyStyle::Envelope => response.json(self.paginated),
+            BodyStyle::Items => response.json(self.paginated.items),
+        }
+    }
+}
+
+impl<T: Serialize> Responder for Paginated<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        PaginatedResponse::from(self).respond_to(req)
+    }
+}
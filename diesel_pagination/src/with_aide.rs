@@ -0,0 +1,23 @@
+use crate::Paginated;
+use aide::{
+    OperationOutput,
+    generate::GenContext,
+    openapi::{Operation, Response},
+};
+use axum::Json;
+use schemars::JsonSchema;
+
+impl<T: JsonSchema> OperationOutput for Paginated<T> {
+    type Inner = Self;
+
+    fn operation_response(ctx: &mut GenContext, operation: &mut Operation) -> Option<Response> {
+        Json::<Self>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, Response)> {
+        Json::<Self>::inferred_responses(ctx, operation)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    Paginated,
+    response::{
+        BodyStyle, PAGE_HEADER, PER_PAGE_HEADER, PageInfo, PaginatedResponse, TOTAL_COUNT_HEADER,
+    },
+};
+use axum::{
+    Json,
+    extract::{OriginalUri, Request},
+    http::{HeaderValue, header::LINK},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+impl<T: Serialize> IntoResponse for PaginatedResponse<T> {
+    fn into_response(self) -> Response {
+        let info = PageInfo::from(&self.paginated);
+        let mut response = match self.body {
+            BodyStyle::Envelope => Json(self.paginated).into_response(),
+            BodyStyle::Items => Json(self.paginated.items).into_response(),
+        };
+
+        let headers = response.headers_mut();
+        headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(info.num_total));
+        headers.insert(PAGE_HEADER, HeaderValue::from(info.page));
+        headers.insert(PER_PAGE_HEADER, HeaderValue::from(info.per_page));
+        response.extensions_mut().insert(info);
+        response
+    }
+}
+
+impl<T: Serialize> IntoResponse for Paginated<T> {
+    fn into_response(self) -> Response {
+        PaginatedResponse::from(self).into_response()
+    }
+}
+
+/// Middleware adding the `Link` header to paginated responses, since the request
+/// URI is not available when converting `Paginated<T>` into a response.
+///
+/// ```ignore
+/// Router::new()
+///     .route("/projects", get(list))
+///     .layer(axum::middleware::from_fn(pagination_links))
+/// ```
+pub async fn pagination_links(request: Request, next: Next) -> Response {
+    let uri = match request.extensions().get::<OriginalUri>() {
+        Some(OriginalUri(uri)) => uri.clone(),
+        None => request.uri().clone(),
+    };
+    let mut response = next.run(request).await;
+
+    if let Some(info) = response.extensions().get::<PageInfo>() {
+        let link = info.link_header(uri.path(), uri.query());
+        if let Ok(link) = HeaderValue::try_from(link) {
+            response.headers_mut().insert(LINK, link);
+        }
+    }
+    response
+}
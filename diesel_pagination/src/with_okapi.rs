@@ -0,0 +1,13 @@
+use crate::Paginated;
+use rocket::serde::json::Json;
+use rocket_okapi::{
+    r#gen::OpenApiGenerator, okapi::openapi3::Responses, response::OpenApiResponderInner,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+impl<T: Serialize + JsonSchema + Send> OpenApiResponderInner for Paginated<T> {
+    fn responses(generator: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        Json::<Self>::responses(generator)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    Paginated,
+    response::{
+        BodyStyle, PAGE_HEADER, PER_PAGE_HEADER, PageInfo, PaginatedResponse, TOTAL_COUNT_HEADER,
+    },
+};
+use rocket::{
+    Request,
+    form::{self, Error},
+    response::{self, Responder, Response},
+    serde::json::Json,
+};
+use serde::Serialize;
+
+pub(crate) fn at_least_one<'v>(value: &Option<i64>) -> form::Result<'v, ()> {
+    match value {
+        Some(value) if *value < 1 => Err(Error::validation("must be at least 1"))?,
+        _ => Ok(()),
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for PaginatedResponse<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let info = PageInfo::from(&self.paginated);
+        let uri = req.uri();
+        let link = info.link_header(uri.path().as_str(), uri.query().map(|query| query.as_str()));
+
+        let body = match self.body {
+            BodyStyle::Envelope => Json(self.paginated).respond_to(req)?,
+            BodyStyle::Items => Json(self.paginated.items).respond_to(req)?,
+        };
+
+        Response::build_from(body)
+            .raw_header("Link", link)
+            .raw_header(TOTAL_COUNT_HEADER, info.num_total.to_string())
+            .raw_header(PAGE_HEADER, info.page.to_string())
+            .raw_header(PER_PAGE_HEADER, info.per_page.to_string())
+            .ok()
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Paginated<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        PaginatedResponse::from(self).respond_to(req)
+    }
+}
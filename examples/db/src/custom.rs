@@ -1,8 +1,8 @@
 use diesel_derive_newtype::DieselNewType;
 use serde::{Deserialize, Serialize};
-use std::{convert::Infallible, str::FromStr};
+use std::{convert::Infallible, fmt, str::FromStr};
 
-#[derive(Debug, Serialize, Deserialize, DieselNewType)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, DieselNewType)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct CustomType(String);
 
@@ -13,3 +13,9 @@ impl FromStr for CustomType {
         Ok(Self(s.to_owned()))
     }
 }
+
+impl fmt::Display for CustomType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
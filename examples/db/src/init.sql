@@ -59,3 +59,55 @@ values
     ('name6', 6, 6, 6, 6, '6', '6', 'c6', 'c6', 'c6'),
     ('name7', 7, 7, 7, 7, '7', '7', 'c7', 'c7', 'c7'),
     ('name8', 8, 8, 8, 8, '8', '8', 'c8', 'c8', 'c8');
+
+create table
+    events (
+        id uuid primary key default uuid_generate_v4 (),
+        name varchar not null,
+        active boolean not null,
+        day date not null,
+        created_at timestamptz not null
+    );
+
+insert into
+    events (name, active, day, created_at)
+values
+    ('launch', true, '2024-01-15', '2024-01-15 10:00:00+00'),
+    ('retro', false, '2024-01-31', '2024-01-31 23:30:00+00'),
+    ('kickoff', true, current_date, now());
+
+create table
+    posts (
+        id uuid primary key default uuid_generate_v4 (),
+        title varchar not null,
+        tags text[] not null,
+        labels text[]
+    );
+
+insert into
+    posts (title, tags, labels)
+values
+    ('diesel', '{rust,sql}', '{tutorial}'),
+    ('axum', '{rust,web}', null),
+    ('postgres', '{sql}', '{reference,tutorial}');
+
+create table
+    shops (
+        id uuid primary key default uuid_generate_v4 (),
+        name varchar not null,
+        attributes jsonb not null
+    );
+
+insert into
+    shops (name, attributes)
+values
+    (
+        'bakery',
+        '{"address": {"city": "Paris"}, "open": true, "tags": ["bread"]}'
+    ),
+    (
+        'florist',
+        '{"address": {"city": "Lyon"}, "open": false, "delivery": 5}'
+    ),
+    ('kiosk', '{"address": {"city": "paris"}}'),
+    ('market', '[1, [2]]');
@@ -13,3 +13,30 @@ diesel::table! {
         multiple_custom -> Varchar,
     }
 }
+
+diesel::table! {
+    events (id) {
+        id -> Uuid,
+        name -> Varchar,
+        active -> Bool,
+        day -> Date,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    posts (id) {
+        id -> Uuid,
+        title -> Varchar,
+        tags -> Array<Text>,
+        labels -> Nullable<Array<Text>>,
+    }
+}
+
+diesel::table! {
+    shops (id) {
+        id -> Uuid,
+        name -> Varchar,
+        attributes -> Jsonb,
+    }
+}
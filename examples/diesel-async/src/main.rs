@@ -1,7 +1,7 @@
 use anyhow::Result;
 use diesel::prelude::*;
 use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
-use diesel_filter::DieselFilter;
+use diesel_filter::{DieselFilter, FilterRuleError};
 use diesel_filter_test_db::{TestDb, custom::CustomType, schema::thingies};
 use serde::{Deserialize, Serialize};
 use tracing::info;
@@ -9,9 +9,10 @@ use uuid::Uuid;
 
 #[derive(DieselFilter, Queryable, Debug, Serialize, Deserialize)]
 #[diesel(table_name = thingies)]
+#[filter_struct(query_string)]
 pub struct Thingy {
     pub id: Uuid,
-    #[filter(insensitive)]
+    #[filter(insensitive, trim, empty_as_none)]
     #[serde(skip)]
     pub name: String,
     #[filter]
@@ -22,7 +23,7 @@ pub struct Thingy {
     pub num64: i64,
     #[filter]
     pub option_num64: Option<i64>,
-    #[filter(multiple, substring, insensitive)]
+    #[filter(multiple(max = 3), substring, insensitive, max_len = 10)]
     pub text: String,
     #[filter]
     pub option_text: Option<String>,
@@ -34,6 +35,19 @@ pub struct Thingy {
     pub multiple_custom: CustomType,
 }
 
+/// Thingies can only be listed by name or by number, not both.
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = thingies)]
+#[filter_struct(require_any(name, num32, text), exclusive(name, num32, text))]
+pub struct SelectiveThingy {
+    #[filter(insensitive)]
+    pub name: String,
+    #[filter]
+    pub num32: i32,
+    #[filter(multiple)]
+    pub text: String,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt().init();
@@ -65,6 +79,39 @@ async fn main() -> Result<()> {
     info!(?results_nonsense);
     assert_eq!(results_nonsense.len(), 0);
 
+    let filters_parsed = ThingyFilters::from_query_str("?num32=2&multiple_custom=c1,c2")?;
+    info!(?filters_parsed);
+    let results_parsed = Thingy::filter(filters_parsed)
+        .get_results::<Thingy>(&mut conn)
+        .await?;
+    info!(?results_parsed);
+    assert_eq!(results_parsed.len(), 1);
+
+    let invalid = ThingyFilters::from_query_str("num32=one&num64=2").unwrap_err();
+    assert_eq!(invalid.errors.len(), 1);
+    assert_eq!(invalid.errors[0].field, "num32");
+
+    let too_many = ThingyFilters::from_query_str("text=1,2,3,4").unwrap_err();
+    info!(%too_many);
+    assert_eq!(too_many.errors[0].message, "more than 3 values");
+    let too_long = ThingyFilters::from_query_str("text=1,0123456789a").unwrap_err();
+    assert_eq!(too_long.errors[0].value, "0123456789a");
+
+    let filters_trimmed = ThingyFilters::from_query_str("name=%20Name1%20&option_text=")?;
+    assert_eq!(filters_trimmed.name.as_deref(), Some("Name1"));
+    let filters_empty = ThingyFilters::from_query_str("name=&multiple_custom=")?;
+    assert_eq!(filters_empty.name, None);
+    assert!(filters_empty.multiple_custom.is_none());
+
+    // An empty list given in code matches nothing, as `= ANY('{}')` does
+    let count_none = Thingy::count_filtered(ThingyFilters {
+        multiple_custom: Some(vec![]),
+        ..Default::default()
+    })
+    .get_result::<i64>(&mut conn)
+    .await?;
+    assert_eq!(count_none, 0);
+
     let filters_all = ThingyFilters::default();
     info!(?filters_all);
     let results_all = Thingy::filter(filters_all)
@@ -73,5 +120,53 @@ async fn main() -> Result<()> {
     info!(?results_all);
     assert_eq!(results_all.len(), 8);
 
+    let count_all = Thingy::count_filtered(ThingyFilters::default())
+        .get_result::<i64>(&mut conn)
+        .await?;
+    assert_eq!(count_all, 8);
+    let exists_nonsense = Thingy::exists_filtered(ThingyFilters {
+        name: Some("aaa".to_owned()),
+        ..Default::default()
+    })
+    .get_result::<bool>(&mut conn)
+    .await?;
+    assert!(!exists_nonsense);
+
+    let unselective = SelectiveThingy::filter(Default::default()).err();
+    info!(?unselective);
+    assert_eq!(
+        unselective,
+        Some(FilterRuleError::RequireAny {
+            fields: vec!["name", "num32", "text"]
+        })
+    );
+    // An empty list doesn't narrow the query down, it is not a selective filter
+    let filters_empty_list = SelectiveThingyFilters {
+        text: Some(vec![]),
+        ..Default::default()
+    };
+    assert!(filters_empty_list.validate().is_err());
+    let filters_both = SelectiveThingyFilters {
+        name: Some("name2".to_owned()),
+        num32: Some(2),
+        ..Default::default()
+    };
+    assert!(filters_both.validate().is_err());
+    let filters_name_empty_list = SelectiveThingyFilters {
+        name: Some("name2".to_owned()),
+        text: Some(vec![]),
+        ..Default::default()
+    };
+    assert!(filters_name_empty_list.validate().is_ok());
+    let results_num2 = SelectiveThingy::filter(SelectiveThingyFilters {
+        num32: Some(2),
+        ..Default::default()
+    })?
+    .select(SelectiveThingy::as_select())
+    .get_results(&mut conn)
+    .await?;
+    info!(?results_num2);
+    assert_eq!(results_num2.len(), 1);
+
     Ok(())
 }
@@ -0,0 +1,63 @@
+//! Checks the `array` filters on Postgres arrays.
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_filter::DieselFilter;
+use diesel_filter_test_db::schema::posts;
+use tracing::info;
+
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = posts)]
+#[filter_struct(query_string, matches)]
+pub struct Post {
+    #[filter]
+    pub title: String,
+    #[filter(array(contains, overlaps, contained_by, max = 5))]
+    pub tags: Vec<String>,
+    #[filter(array(overlaps), trim, lowercase)]
+    pub labels: Option<Vec<String>>,
+}
+
+fn titles(conn: &mut PgConnection, query: &str) -> Result<Vec<String>> {
+    let mut posts = Post::filter(PostFilters::from_query_str(query)?)
+        .select(Post::as_select())
+        .get_results(conn)?;
+    info!(query, ?posts);
+    assert!(
+        posts
+            .iter()
+            .all(|post| PostFilters::from_query_str(query).unwrap().matches(post))
+    );
+    posts.sort_by(|a, b| a.title.cmp(&b.title));
+    Ok(posts.into_iter().map(|post| post.title).collect())
+}
+
+pub fn check_arrays(conn: &mut PgConnection) -> Result<()> {
+    assert_eq!(titles(conn, "tags_contains=rust,sql")?, ["diesel"]);
+    assert_eq!(
+        titles(conn, "tags_overlaps=web,sql")?,
+        ["axum", "diesel", "postgres"]
+    );
+    assert_eq!(
+        titles(conn, "tags_contained_by=rust,sql")?,
+        ["diesel", "postgres"]
+    );
+    assert_eq!(
+        titles(conn, "labels_overlaps=%20Tutorial%20")?,
+        ["diesel", "postgres"]
+    );
+
+    // A NULL array overlaps nothing
+    let axum = PostFilters::from_query_str("title=axum")?;
+    let post = Post::filter(axum)
+        .select(Post::as_select())
+        .get_result(conn)?;
+    assert!(!PostFilters::from_query_str("labels_overlaps=tutorial")?.matches(&post));
+    assert!(post.labels.is_none());
+
+    let invalid = PostFilters::from_query_str("tags_overlaps=a,b,c,d,e,f").unwrap_err();
+    info!(%invalid);
+    assert_eq!(invalid.errors[0].field, "tags_overlaps");
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Checks the `lenient` parsing of booleans and dates, and the `date` filters.
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_filter::{
+    DieselFilter,
+    dates::{Day, Moment, Period},
+};
+use diesel_filter_test_db::schema::events;
+use tracing::info;
+
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = events)]
+#[filter_struct(query_string, matches)]
+pub struct Event {
+    #[filter]
+    pub name: String,
+    #[filter(lenient)]
+    pub active: bool,
+    #[filter(lenient, multiple, date)]
+    pub day: NaiveDate,
+    #[filter(lenient(range), date)]
+    pub created_at: DateTime<Utc>,
+}
+
+/// `retro` was created on January 31st in UTC, but on February 1st in Paris.
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = events)]
+#[filter_struct(query_string)]
+pub struct ParisEvent {
+    pub name: String,
+    #[filter(date(tz = chrono_tz::Europe::Paris))]
+    pub created_at: DateTime<Utc>,
+}
+
+pub fn check_dates(conn: &mut PgConnection) -> Result<()> {
+    let filters_active = EventFilters::from_query_str("active=yes")?;
+    let results_active = Event::filter(filters_active)
+        .select(Event::as_select())
+        .get_results(conn)?;
+    info!(?results_active);
+    assert_eq!(results_active.len(), 2);
+    assert!(results_active.iter().all(|event| event.active));
+
+    let filters_days = EventFilters::from_query_str("active=off&day=2024-01-15,2024-01-31")?;
+    let results_days = Event::filter(filters_days)
+        .select(Event::as_select())
+        .get_results(conn)?;
+    assert_eq!(results_days.len(), 1);
+    assert_eq!(results_days[0].name, "retro");
+    assert_eq!(results_days[0].day.to_string(), "2024-01-31");
+    assert_eq!(results_days[0].created_at.date_naive(), results_days[0].day);
+
+    let filters_launch = EventFilters::from_query_str("created_at=2024-01-15T10:00:00Z")?;
+    let count_launch = Event::count_filtered(filters_launch).get_result::<i64>(conn)?;
+    assert_eq!(count_launch, 1);
+
+    // Date-only values of date-times are the start of the day
+    let midnight = EventFilters::from_query_str("created_at=2024-01-15")?.created_at;
+    assert_eq!(
+        midnight.map(|moment| moment.to_rfc3339()).as_deref(),
+        Some("2024-01-15T00:00:00+00:00")
+    );
+
+    // Or the start of the next day for the end of a range, `retro` on the 31st included
+    let range = "created_at_since=2024-01-15&created_at_until=2024-01-31";
+    let filters_range = EventFilters::from_query_str(range)?;
+    assert_eq!(
+        filters_range
+            .created_at_until
+            .map(|moment| moment.to_rfc3339())
+            .as_deref(),
+        Some("2024-02-01T00:00:00+00:00")
+    );
+    let results_range = Event::filter(EventFilters::from_query_str(range)?)
+        .select(Event::as_select())
+        .get_results(conn)?;
+    info!(?results_range);
+    assert_eq!(results_range.len(), 2);
+    assert!(
+        results_range
+            .iter()
+            .all(|event| filters_range.matches(event))
+    );
+    let filters_until = EventFilters::from_query_str("created_at_until=2024-01-31T12:00")?;
+    let count_until = Event::count_filtered(filters_until).get_result::<i64>(conn)?;
+    assert_eq!(count_until, 1);
+
+    let filters_on = EventFilters::from_query_str("created_at_on=2024-01-15")?;
+    let results_on = Event::filter(filters_on)
+        .select(Event::as_select())
+        .get_results(conn)?;
+    assert_eq!(results_on.len(), 1);
+    assert_eq!(results_on[0].name, "launch");
+
+    let filters_january =
+        EventFilters::from_query_str("day_after=2024-01-01&day_before=2024-02-01")?;
+    let count_january = Event::count_filtered(filters_january).get_result::<i64>(conn)?;
+    assert_eq!(count_january, 2);
+
+    // `kickoff` is inserted at the current time, a week ago is never on the other side of it
+    let filters_recent = EventFilters::from_query_str("created_at_within=7d")?;
+    let results_recent = Event::filter(filters_recent)
+        .select(Event::as_select())
+        .get_results(conn)?;
+    info!(?results_recent);
+    assert_eq!(results_recent.len(), 1);
+    assert_eq!(results_recent[0].name, "kickoff");
+
+    // Calendar periods are checked against a fixed current time, not the wall clock
+    let now: DateTime<Utc> = "2024-02-10T12:00:00Z".parse()?;
+    let (start, end) = Period::LastMonth.bounds_at::<DateTime<Utc>, _>(&Utc, now);
+    assert_eq!(start.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+    assert_eq!(end.to_rfc3339(), "2024-02-01T00:00:00+00:00");
+    let filters_last_month = EventFilters {
+        created_at_after: Some(Moment::Instant(start.fixed_offset())),
+        created_at_before: Some(Moment::Instant(end.fixed_offset())),
+        ..Default::default()
+    };
+    let count_last_month = Event::count_filtered(filters_last_month).get_result::<i64>(conn)?;
+    assert_eq!(count_last_month, 2);
+    let (start, end) = Period::ThisYear.bounds_at::<NaiveDate, _>(&Utc, now);
+    assert_eq!(
+        (start.to_string(), end.to_string()),
+        ("2024-01-01".to_owned(), "2025-01-01".to_owned())
+    );
+    assert_eq!(Day::Today(-10).date_at(&Utc, now).to_string(), "2024-01-31");
+
+    // At 23:30 on January 31st in UTC, it's already February 1st in Paris
+    let late: DateTime<Utc> = "2024-01-31T23:30:00Z".parse()?;
+    let paris_today = Day::Today(0).date_at(&chrono_tz::Europe::Paris, late);
+    assert_eq!(paris_today.to_string(), "2024-02-01");
+    let (start, end) = Day::Today(0).bounds_at::<DateTime<Utc>, _>(&chrono_tz::Europe::Paris, late);
+    let filters_paris_today = EventFilters {
+        created_at_after: Some(Moment::Instant(start.fixed_offset())),
+        created_at_before: Some(Moment::Instant(end.fixed_offset())),
+        ..Default::default()
+    };
+    let results_paris_today = Event::filter(filters_paris_today)
+        .select(Event::as_select())
+        .get_results(conn)?;
+    assert_eq!(results_paris_today.len(), 1);
+    assert_eq!(results_paris_today[0].name, "retro");
+
+    let filters_paris = ParisEventFilters::from_query_str("created_at_on=2024-02-01")?;
+    let results_paris = ParisEvent::filter(filters_paris)
+        .select(ParisEvent::as_select())
+        .get_results(conn)?;
+    assert_eq!(results_paris.len(), 1);
+    assert_eq!(results_paris[0].name, "retro");
+    assert_eq!(
+        results_paris[0].created_at.date_naive().to_string(),
+        "2024-01-31"
+    );
+
+    let invalid = EventFilters::from_query_str("active=maybe").unwrap_err();
+    info!(%invalid);
+    assert_eq!(invalid.errors[0].field, "active");
+
+    Ok(())
+}
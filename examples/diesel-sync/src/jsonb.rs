@@ -0,0 +1,126 @@
+//! Checks the `jsonb` filters.
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_filter::DieselFilter;
+use diesel_filter_test_db::schema::shops;
+use serde_json::Value;
+use tracing::info;
+
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = shops)]
+#[filter_struct(query_string, matches)]
+pub struct Shop {
+    #[filter]
+    pub name: String,
+    #[filter(jsonb(path = "address.city", path = "open"), insensitive, multiple)]
+    pub attributes: Value,
+}
+
+/// A named path, with a single key for `attributes_has_key` although the paths are `multiple`.
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = shops)]
+#[filter_struct(query_string, matches)]
+pub struct ShopAddress {
+    pub name: String,
+    #[filter(
+        jsonb(path(key = "address.city", name = "city"), has_any_key = false),
+        multiple,
+        max_len = 40
+    )]
+    pub attributes: Value,
+}
+
+/// The shops matching `query` in the database, which must be those `matches` keeps.
+fn names(conn: &mut PgConnection, query: &str) -> Result<Vec<String>> {
+    let mut shops = Shop::filter(ShopFilters::from_query_str(query)?)
+        .select(Shop::as_select())
+        .get_results(conn)?;
+    info!(query, ?shops);
+    shops.sort_by(|a, b| a.name.cmp(&b.name));
+    let names: Vec<_> = shops.into_iter().map(|shop| shop.name).collect();
+
+    let filters = ShopFilters::from_query_str(query)?;
+    let mut matching: Vec<_> = shops::table
+        .select(Shop::as_select())
+        .load(conn)?
+        .into_iter()
+        .filter(|shop| filters.matches(shop))
+        .map(|shop| shop.name)
+        .collect();
+    matching.sort();
+    assert_eq!(names, matching, "{query}");
+    Ok(names)
+}
+
+pub fn check_jsonb(conn: &mut PgConnection) -> Result<()> {
+    assert_eq!(
+        names(conn, "attributes_address_city=PARIS")?,
+        ["bakery", "kiosk"]
+    );
+    assert_eq!(
+        names(conn, "attributes_address_city=lyon,nantes")?,
+        ["florist"]
+    );
+    // Non-string values are compared as JSON
+    assert_eq!(names(conn, "attributes_open=true")?, ["bakery"]);
+    assert_eq!(
+        names(conn, "attributes_contains={\"tags\":[\"bread\"]}")?,
+        ["bakery"]
+    );
+    assert_eq!(
+        names(
+            conn,
+            "attributes_contains={\"address\":{\"city\":\"Lyon\"}}"
+        )?,
+        ["florist"]
+    );
+    // An array contains its primitive values only at the top level
+    assert!(names(conn, "attributes_contains={\"tags\":\"bread\"}")?.is_empty());
+    assert_eq!(names(conn, "attributes_contains=[1]")?, ["market"]);
+    assert_eq!(names(conn, "attributes_contains=1")?, ["market"]);
+    assert!(names(conn, "attributes_contains=[2]")?.is_empty());
+    assert_eq!(names(conn, "attributes_contains=[[2]]")?, ["market"]);
+    assert_eq!(
+        names(conn, "attributes_has_key=delivery,tags")?,
+        ["bakery", "florist"]
+    );
+
+    let kiosk = Shop::filter(ShopFilters::from_query_str("name=kiosk")?)
+        .select(Shop::as_select())
+        .get_result(conn)?;
+    assert!(!ShopFilters::from_query_str("attributes_open=false")?.matches(&kiosk));
+    assert!(kiosk.attributes.get("open").is_none());
+
+    let invalid = ShopFilters::from_query_str("attributes_contains={city}").unwrap_err();
+    info!(%invalid);
+    assert_eq!(invalid.errors[0].field, "attributes_contains");
+
+    let filters_cities = ShopAddressFilters::from_query_str("attributes_city=Lyon,Paris")?;
+    let mut results_cities = ShopAddress::filter(filters_cities)
+        .select(ShopAddress::as_select())
+        .get_results(conn)?;
+    results_cities.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(results_cities.len(), 2);
+    assert_eq!(results_cities[0].name, "bakery");
+    assert_eq!(results_cities[1].name, "florist");
+    assert_eq!(results_cities[1].attributes["address"]["city"], "Lyon");
+
+    // The comma is part of the single key
+    let filters_key = ShopAddressFilters::from_query_str("attributes_has_key=delivery,tags")?;
+    assert_eq!(
+        filters_key.attributes_has_key.as_deref(),
+        Some("delivery,tags")
+    );
+    let count_key = ShopAddress::count_filtered(filters_key).get_result::<i64>(conn)?;
+    assert_eq!(count_key, 0);
+
+    let too_long = ShopAddressFilters::from_query_str(
+        "attributes_contains={\"address\":{\"city\":\"Saint-Remy-de-Provence\"}}",
+    )
+    .unwrap_err();
+    info!(%too_long);
+    assert_eq!(too_long.errors[0].field, "attributes_contains");
+
+    Ok(())
+}
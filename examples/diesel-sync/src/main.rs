@@ -1,3 +1,9 @@
+mod arrays;
+mod dates;
+mod jsonb;
+mod matches;
+mod scopes;
+
 use anyhow::Result;
 use diesel::prelude::*;
 use diesel_filter::DieselFilter;
@@ -9,6 +15,7 @@ use uuid::Uuid;
 
 #[derive(DieselFilter, Queryable, Debug, Serialize, Deserialize)]
 #[diesel(table_name = thingies)]
+#[filter_struct(query_string, matches)]
 pub struct Thingy {
     pub id: Uuid,
     #[filter(insensitive)]
@@ -16,6 +23,7 @@ pub struct Thingy {
     pub name: String,
     #[filter]
     pub num32: i32,
+    /// An optional number
     #[filter]
     pub option_num32: Option<i32>,
     #[filter]
@@ -63,12 +71,65 @@ async fn main() -> Result<()> {
         info!(?results_nonsense);
         assert_eq!(results_nonsense.len(), 0);
 
+        let filters_query = ThingyFilters {
+            num32: Some(1),
+            text: Some(vec!["a b".to_owned(), "c".to_owned()]),
+            ..Default::default()
+        };
+        assert_eq!(filters_query.to_query_string(), "num32=1&text=a%20b%2Cc");
+
+        // Commas within the values of `multiple` filters are escaped
+        let filters_commas = ThingyFilters {
+            text: Some(vec!["a,b".to_owned(), "c\\".to_owned(), "d".to_owned()]),
+            ..Default::default()
+        };
+        let query_commas = filters_commas.to_query_string();
+        assert_eq!(query_commas, "text=a%5C%2Cb%2Cc%5C%5C%2Cd");
+        let parsed_commas = ThingyFilters::from_query_str(&query_commas)?;
+        assert_eq!(parsed_commas.text, filters_commas.text);
+
+        let option_num32 = &ThingyFilters::FIELDS[2];
+        assert_eq!(option_num32.name, "option_num32");
+        assert_eq!(option_num32.value_type, "i32");
+        assert!(option_num32.nullable);
+        assert_eq!(option_num32.description, Some("An optional number"));
+
         let filters_all = ThingyFilters::default();
         info!(?filters_all);
         let results_all = Thingy::filter(filters_all).get_results::<Thingy>(&mut conn)?;
         info!(?results_all);
         assert_eq!(results_all.len(), 8);
 
+        matches::check_matches(&mut conn)?;
+        scopes::check_scopes(&mut conn)?;
+        dates::check_dates(&mut conn)?;
+        arrays::check_arrays(&mut conn)?;
+        jsonb::check_jsonb(&mut conn)?;
+
+        conn.test_transaction(|conn| -> Result<()> {
+            let filters_text1 = || ThingyFilters {
+                text: Some(vec!["1".to_owned()]),
+                ..Default::default()
+            };
+            let count_text1 = Thingy::count_filtered(filters_text1()).get_result::<i64>(conn)?;
+            assert!(Thingy::exists_filtered(filters_text1()).get_result::<bool>(conn)?);
+
+            let updated =
+                Thingy::update_filtered(filters_text1(), thingies::num64.eq(42)).execute(conn)?;
+            assert_eq!(updated as i64, count_text1);
+            let filters_updated = ThingyFilters {
+                num64: Some(42),
+                ..Default::default()
+            };
+            let count_updated = Thingy::count_filtered(filters_updated).get_result::<i64>(conn)?;
+            assert_eq!(count_updated, count_text1);
+
+            let deleted = Thingy::delete_filtered(filters_text1()).execute(conn)?;
+            assert_eq!(deleted as i64, count_text1);
+            assert!(!Thingy::exists_filtered(filters_text1()).get_result::<bool>(conn)?);
+            Ok(())
+        });
+
         Ok(())
     })
     .await?
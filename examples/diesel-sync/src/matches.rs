@@ -0,0 +1,102 @@
+//! Checks that `ThingyFilters::matches` agrees with the SQL of `Thingy::filter`,
+//! on random rows and filters.
+
+use crate::{Thingy, ThingyFilters};
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_filter_test_db::{custom::CustomType, schema::thingies};
+use fastrand::Rng;
+use tracing::info;
+use uuid::Uuid;
+
+// Includes the wildcards and the escape character of `LIKE`
+const CHARS: &[char] = &['a', 'A', 'b', '%', '_', '\\', ' '];
+
+fn string(rng: &mut Rng) -> String {
+    let mut string: String = (0..rng.usize(0..4))
+        .map(|_| CHARS[rng.usize(..CHARS.len())])
+        .collect();
+    // Postgres rejects patterns ending with the escape character
+    if string.ends_with('\\') {
+        string.push('a');
+    }
+    string
+}
+
+fn custom(rng: &mut Rng) -> CustomType {
+    let Ok(custom) = format!("c{}", rng.u8(0..4)).parse();
+    custom
+}
+
+fn maybe<T>(rng: &mut Rng, value: impl FnOnce(&mut Rng) -> T) -> Option<T> {
+    rng.bool().then(|| value(rng))
+}
+
+fn some_of<T>(rng: &mut Rng, value: impl Fn(&mut Rng) -> T) -> Vec<T> {
+    (0..rng.usize(0..3)).map(|_| value(rng)).collect()
+}
+
+fn random_filters(rng: &mut Rng) -> ThingyFilters {
+    ThingyFilters {
+        name: maybe(rng, string),
+        num32: maybe(rng, |rng| rng.i32(0..4)),
+        option_num32: maybe(rng, |rng| rng.i32(0..4)),
+        num64: maybe(rng, |rng| rng.i64(0..4)),
+        option_num64: maybe(rng, |rng| rng.i64(0..4)),
+        text: maybe(rng, |rng| some_of(rng, string)),
+        option_text: maybe(rng, string),
+        custom: maybe(rng, custom),
+        option_custom: maybe(rng, custom),
+        multiple_custom: maybe(rng, |rng| some_of(rng, custom)),
+    }
+}
+
+pub fn check_matches(conn: &mut PgConnection) -> Result<()> {
+    let mut rng = Rng::with_seed(40);
+
+    conn.test_transaction(|conn| -> Result<()> {
+        for _ in 0..50 {
+            diesel::insert_into(thingies::table)
+                .values((
+                    thingies::name.eq(string(&mut rng)),
+                    thingies::num32.eq(rng.i32(0..4)),
+                    thingies::option_num32.eq(maybe(&mut rng, |rng| rng.i32(0..4))),
+                    thingies::num64.eq(rng.i64(0..4)),
+                    thingies::option_num64.eq(maybe(&mut rng, |rng| rng.i64(0..4))),
+                    thingies::text.eq(string(&mut rng)),
+                    thingies::option_text.eq(maybe(&mut rng, string)),
+                    thingies::custom.eq(custom(&mut rng)),
+                    thingies::option_custom.eq(maybe(&mut rng, custom)),
+                    thingies::multiple_custom.eq(custom(&mut rng)),
+                ))
+                .execute(conn)?;
+        }
+        let all = thingies::table.load::<Thingy>(conn)?;
+
+        let mut matched = 0;
+        for _ in 0..1000 {
+            // `filter` takes the filters by value, the same ones are generated twice
+            let mut same_rng = rng.clone();
+            let filters = random_filters(&mut rng);
+
+            let mut expected: Vec<Uuid> = Thingy::filter(random_filters(&mut same_rng))
+                .select(thingies::id)
+                .load(conn)?;
+            expected.sort();
+            let mut found: Vec<Uuid> = all
+                .iter()
+                .filter(|thingy| filters.matches(thingy))
+                .map(|thingy| thingy.id)
+                .collect();
+            found.sort();
+
+            assert_eq!(found, expected, "{filters:?}");
+            matched += found.len();
+        }
+        info!(matched, "matches agrees with Postgres");
+
+        Ok(())
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Checks the `filter_scope` predicates, with `custom` standing for a tenant
+//! and the rows without `option_num32` for deleted ones.
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_filter::DieselFilter;
+use diesel_filter_test_db::{custom::CustomType, schema::thingies};
+use tracing::info;
+
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = thingies)]
+#[filter_scope(is_not_null = option_num32, eq = custom)]
+#[filter_struct(matches)]
+pub struct ScopedThingy {
+    #[filter(insensitive)]
+    pub name: String,
+    pub option_num32: Option<i32>,
+    pub custom: CustomType,
+}
+
+fn custom(value: &str) -> CustomType {
+    let Ok(custom) = value.parse();
+    custom
+}
+
+pub fn check_scopes(conn: &mut PgConnection) -> Result<()> {
+    let results_c1 = ScopedThingy::filter(custom("c1"), Default::default())
+        .select(ScopedThingy::as_select())
+        .get_results(conn)?;
+    info!(?results_c1);
+    assert_eq!(results_c1.len(), 1);
+    assert!(ScopedThingyFilters::default().matches(&custom("c1"), &results_c1[0]));
+    assert!(!ScopedThingyFilters::default().matches(&custom("c3"), &results_c1[0]));
+
+    // `name3` has no `option_num32`
+    let filters_name3 = || ScopedThingyFilters {
+        name: Some("name3".to_owned()),
+    };
+    let count_c3 =
+        ScopedThingy::count_filtered(custom("c3"), filters_name3()).get_result::<i64>(conn)?;
+    assert_eq!(count_c3, 0);
+
+    let results_unscoped = ScopedThingy::unscoped(filters_name3())
+        .select(ScopedThingy::as_select())
+        .get_results(conn)?;
+    info!(?results_unscoped);
+    assert_eq!(results_unscoped.len(), 1);
+    assert!(!filters_name3().matches(&custom("c3"), &results_unscoped[0]));
+
+    Ok(())
+}
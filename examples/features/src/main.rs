@@ -0,0 +1,118 @@
+//! Builds the filters of the derive with each web framework integration, alone or
+//! combined, e.g. `cargo run --features rocket,axum`. No database is needed.
+
+#[cfg(feature = "actix")]
+mod with_actix;
+#[cfg(feature = "aide")]
+mod with_aide;
+#[cfg(feature = "axum")]
+mod with_axum;
+#[cfg(feature = "async-graphql")]
+mod with_graphql;
+#[cfg(feature = "okapi")]
+mod with_okapi;
+#[cfg(feature = "rocket")]
+mod with_rocket;
+#[cfg(feature = "schemars")]
+mod with_schemars;
+#[cfg(feature = "utoipa")]
+mod with_utoipa;
+
+use anyhow::Result;
+use diesel::{debug_query, pg::Pg, prelude::*};
+use diesel_filter::DieselFilter;
+use diesel_filter_test_db::schema::thingies;
+use diesel_pagination::{Paginate, Paginated, PaginationParams};
+#[cfg(feature = "rocket")]
+use rocket::FromForm;
+use tracing::info;
+use uuid::Uuid;
+
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[cfg_attr(feature = "async-graphql", derive(async_graphql::SimpleObject))]
+#[diesel(table_name = thingies)]
+#[filter_struct(query_string)]
+pub struct Thingy {
+    pub id: Uuid,
+    #[filter(insensitive)]
+    pub name: String,
+    #[filter]
+    pub num32: i32,
+    #[filter(multiple, substring)]
+    pub text: String,
+    #[filter]
+    pub option_text: Option<String>,
+}
+
+/// Limits enforced by each integration, `multiple = true` being the form of
+/// `multiple` without options.
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = thingies)]
+#[filter_struct(query_string)]
+pub struct Limited {
+    pub id: Uuid,
+    #[filter(max_len = 8)]
+    pub name: String,
+    #[filter(multiple = true)]
+    pub num32: i32,
+    #[filter(multiple(max = 2), max_len = 8)]
+    pub text: String,
+}
+
+/// Values normalized by each integration.
+#[derive(DieselFilter, Queryable, Selectable, Debug)]
+#[diesel(table_name = thingies)]
+#[filter_struct(query_string, trim)]
+pub struct Normalized {
+    pub id: Uuid,
+    #[filter(empty_as_none, lowercase)]
+    pub name: String,
+    #[filter(multiple(max = 2))]
+    pub text: String,
+}
+
+/// The SQL of the filtered query, as the single item of a page.
+fn search(filters: ThingyFilters, pagination: PaginationParams) -> Paginated<String> {
+    let query = Thingy::filter(filters);
+    vec![debug_query::<Pg, _>(&query).to_string()]
+        .paginate(pagination)
+        .into_paginated()
+}
+
+fn main() -> Result<()> {
+    tracing_subscriber::fmt().init();
+
+    let filters = ThingyFilters::from_query_str("name=thingy&text=a,b")?;
+    let page = search(filters, PaginationParams::default());
+    info!(query = page.items[0]);
+
+    let limited = LimitedFilters::from_query_str("num32=1,2,3&text=a,b")?;
+    assert_eq!(limited.num32, Some(vec![1, 2, 3]));
+    assert!(LimitedFilters::from_query_str("text=a,b,c").is_err());
+    assert!(LimitedFilters::from_query_str("name=toolongname").is_err());
+    assert!(LimitedFilters::from_query_str("text=a,toolongtext").is_err());
+
+    let normalized = NormalizedFilters::from_query_str("name=%20Thingy%20&text=%20a%20,b")?;
+    assert_eq!(normalized.name.as_deref(), Some("thingy"));
+    assert_eq!(normalized.text, Some(vec!["a".to_owned(), "b".to_owned()]));
+    assert_eq!(NormalizedFilters::from_query_str("name=%20")?.name, None);
+
+    #[cfg(feature = "rocket")]
+    with_rocket::check_rocket()?;
+    #[cfg(feature = "actix")]
+    with_actix::check_actix()?;
+    #[cfg(feature = "axum")]
+    with_axum::check_axum()?;
+    #[cfg(feature = "async-graphql")]
+    with_graphql::check_graphql()?;
+    #[cfg(feature = "utoipa")]
+    with_utoipa::check_utoipa()?;
+    #[cfg(feature = "schemars")]
+    with_schemars::check_schemars()?;
+    #[cfg(feature = "aide")]
+    with_aide::check_aide()?;
+    #[cfg(feature = "okapi")]
+    with_okapi::check_okapi()?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! The `Filters` and `ListQuery` extractors of actix.
+
+use crate::{LimitedFilters, NormalizedFilters, ThingyFilters, search};
+use actix_web::{App, web};
+use anyhow::Result;
+use diesel_filter::actix::{Filters, ListQuery};
+use diesel_pagination::{Paginated, PaginationParams};
+
+async fn find(Filters(filters): Filters<ThingyFilters>) -> Paginated<String> {
+    search(filters, PaginationParams::default())
+}
+
+async fn list(
+    ListQuery {
+        filters,
+        pagination,
+    }: ListQuery<ThingyFilters>,
+) -> Paginated<String> {
+    search(filters, pagination)
+}
+
+pub fn check_actix() -> Result<()> {
+    let _app = App::new()
+        .route("/thingies/find", web::get().to(find))
+        .route("/thingies", web::get().to(list));
+
+    let web::Query(filters) = web::Query::<ThingyFilters>::from_query("name=thingy&text=a,b")?;
+    assert_eq!(filters.name.as_deref(), Some("thingy"));
+    assert_eq!(filters.text, Some(vec!["a".to_owned(), "b".to_owned()]));
+
+    assert!(web::Query::<LimitedFilters>::from_query("num32=1,2,3").is_ok());
+    assert!(web::Query::<LimitedFilters>::from_query("text=a,b,c").is_err());
+    assert!(web::Query::<LimitedFilters>::from_query("text=toolongtext").is_err());
+
+    let web::Query(normalized) =
+        web::Query::<NormalizedFilters>::from_query("name=%20Thingy%20&text=%20a%20,b")?;
+    assert_eq!(normalized.name.as_deref(), Some("thingy"));
+    assert_eq!(normalized.text, Some(vec!["a".to_owned(), "b".to_owned()]));
+
+    Ok(())
+}
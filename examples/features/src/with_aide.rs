@@ -0,0 +1,57 @@
+//! The `ListQuery` extractor and `Paginated` response documented with aide.
+
+use crate::{ThingyFilters, search};
+use aide::{
+    axum::{ApiRouter, routing::get},
+    openapi::{OpenApi, Parameter, ReferenceOr},
+};
+use anyhow::{Result, anyhow};
+use diesel_filter::axum::ListQuery;
+use diesel_pagination::Paginated;
+
+async fn list(
+    ListQuery {
+        filters,
+        pagination,
+        ..
+    }: ListQuery<ThingyFilters>,
+) -> Paginated<String> {
+    search(filters, pagination)
+}
+
+pub fn check_aide() -> Result<()> {
+    let mut api = OpenApi::default();
+    let _router: axum::Router = ApiRouter::new()
+        .api_route("/thingies", get(list))
+        .finish_api(&mut api);
+
+    let operation = api
+        .paths
+        .as_ref()
+        .and_then(|paths| paths.paths.get("/thingies"))
+        .and_then(|path| match path {
+            ReferenceOr::Item(path) => path.get.as_ref(),
+            ReferenceOr::Reference { .. } => None,
+        })
+        .ok_or_else(|| anyhow!("`/thingies` is not documented"))?;
+    let parameters = operation
+        .parameters
+        .iter()
+        .filter_map(|parameter| match parameter {
+            ReferenceOr::Item(Parameter::Query { parameter_data, .. }) => {
+                Some(parameter_data.name.as_str())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for name in ["name", "text", "page", "per_page"] {
+        assert!(parameters.contains(&name), "{name} in {parameters:?}");
+    }
+    let responses = operation
+        .responses
+        .as_ref()
+        .ok_or_else(|| anyhow!("no responses"))?;
+    assert!(!responses.responses.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! `Query` and the `ListQuery` extractor of axum.
+
+use crate::{LimitedFilters, NormalizedFilters, ThingyFilters, search};
+use anyhow::Result;
+use axum::{Router, extract::Query, http::Uri, routing::get};
+use diesel_filter::axum::ListQuery;
+use diesel_pagination::{Paginated, pagination_links};
+
+async fn list(
+    ListQuery {
+        filters,
+        pagination,
+        ..
+    }: ListQuery<ThingyFilters>,
+) -> Paginated<String> {
+    search(filters, pagination)
+}
+
+pub fn check_axum() -> Result<()> {
+    // Without the middleware, pages are sent without their `Link` header
+    let _router: Router = Router::new()
+        .route("/thingies", get(list))
+        .layer(axum::middleware::from_fn(pagination_links));
+
+    let uri: Uri = "/thingies?name=thingy&text=a,b".parse()?;
+    let Query(filters) = Query::<ThingyFilters>::try_from_uri(&uri)?;
+    assert_eq!(filters.name.as_deref(), Some("thingy"));
+    assert_eq!(filters.text, Some(vec!["a".to_owned(), "b".to_owned()]));
+
+    let limited = |uri: &str| Query::<LimitedFilters>::try_from_uri(&uri.parse().unwrap());
+    assert!(limited("/thingies?num32=1,2,3").is_ok());
+    assert!(limited("/thingies?text=a,b,c").is_err());
+    assert!(limited("/thingies?name=toolongname").is_err());
+
+    let uri: Uri = "/thingies?name=%20Thingy%20&text=%20a%20,b".parse()?;
+    let Query(normalized) = Query::<NormalizedFilters>::try_from_uri(&uri)?;
+    assert_eq!(normalized.name.as_deref(), Some("thingy"));
+    assert_eq!(normalized.text, Some(vec!["a".to_owned(), "b".to_owned()]));
+
+    Ok(())
+}
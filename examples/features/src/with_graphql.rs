@@ -0,0 +1,63 @@
+//! The `InputObject` of the filters and a Relay connection resolver of async-graphql.
+
+use crate::{LimitedFilters, NormalizedFilters, Thingy, ThingyFilters};
+use anyhow::{Result, anyhow};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, InputType, Object, Schema, value};
+use diesel::{Connection as _, prelude::*};
+use diesel_filter_test_db::schema::thingies;
+use diesel_pagination::{Connection, ConnectionArgs, PaginateConnection};
+
+/// Database url given to the schema.
+pub struct DatabaseUrl(pub String);
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn thingies(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<ThingyFilters>,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> async_graphql::Result<Connection<Thingy>> {
+        let mut conn = PgConnection::establish(&ctx.data::<DatabaseUrl>()?.0)?;
+        let args = ConnectionArgs {
+            first,
+            after,
+            last,
+            before,
+        };
+        Thingy::filter(filters.unwrap_or_default())
+            .select(Thingy::as_select())
+            .paginate_connection(thingies::id, args)
+            .max_page_size(20)
+            .load_connection(&mut conn, |thingy: &Thingy| thingy.id)
+    }
+}
+
+pub fn check_graphql() -> Result<()> {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(DatabaseUrl(String::new()))
+        .finish();
+    let sdl = schema.sdl();
+    assert!(sdl.contains("input ThingyFilters"));
+    assert!(sdl.contains("text: [String!]"));
+    assert!(sdl.contains("type ThingyConnection"));
+
+    let limited = |value| LimitedFilters::parse(Some(value));
+    assert!(limited(value!({ "num32": [1, 2, 3] })).is_ok());
+    assert!(limited(value!({ "text": ["a", "b", "c"] })).is_err());
+    assert!(limited(value!({ "text": ["a", "toolongtext"] })).is_err());
+    assert!(limited(value!({ "name": "toolongname" })).is_err());
+
+    let normalized =
+        NormalizedFilters::parse(Some(value!({ "name": " Thingy ", "text": [" a ", "b"] })))
+            .map_err(|err| anyhow!("{}", err.into_server_error(Default::default()).message))?;
+    assert_eq!(normalized.name.as_deref(), Some("thingy"));
+    assert_eq!(normalized.text, Some(vec!["a".to_owned(), "b".to_owned()]));
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! The rocket form of the filters and `Paginated` response documented with okapi.
+
+use crate::{ThingyFilters, search};
+use anyhow::{Result, anyhow};
+use diesel_pagination::{Paginated, PaginationParams};
+use rocket::get;
+use rocket_okapi::{
+    okapi::openapi3::{Parameter, ParameterValue, RefOr},
+    openapi, openapi_get_routes_spec,
+};
+
+#[openapi]
+#[get("/thingies?<filters>&<pagination..>")]
+fn list(filters: ThingyFilters, pagination: PaginationParams) -> Paginated<String> {
+    search(filters, pagination)
+}
+
+pub fn check_okapi() -> Result<()> {
+    let (routes, spec) = openapi_get_routes_spec![list];
+    let _rocket = rocket::build().mount("/", routes);
+
+    let operation = spec
+        .paths
+        .get("/thingies")
+        .and_then(|path| path.get.as_ref())
+        .ok_or_else(|| anyhow!("`/thingies` is not documented"))?;
+    let parameters = operation
+        .parameters
+        .iter()
+        .filter_map(|parameter| match parameter {
+            RefOr::Object(Parameter {
+                name,
+                value: ParameterValue::Schema { .. },
+                ..
+            }) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for name in ["name", "text", "page", "per_page"] {
+        assert!(parameters.contains(&name), "{name} in {parameters:?}");
+    }
+    assert!(operation.responses.responses.contains_key("200"));
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! The filters as a rocket form, along with the pagination parameters.
+
+use crate::{LimitedFilters, NormalizedFilters, ThingyFilters, search};
+use anyhow::{Result, anyhow};
+use diesel_pagination::{Paginated, PaginationParams};
+use rocket::{form::Form, get, http::RawStr, routes};
+
+#[get("/thingies?<filters>&<pagination..>")]
+fn list(filters: ThingyFilters, pagination: PaginationParams) -> Paginated<String> {
+    search(filters, pagination)
+}
+
+pub fn check_rocket() -> Result<()> {
+    let _rocket = rocket::build().mount("/", routes![list]);
+
+    let filters = Form::<ThingyFilters>::parse_encoded(RawStr::new("name=thingy&text=a&text=b"))
+        .map_err(|err| anyhow!("{err}"))?;
+    assert_eq!(filters.name.as_deref(), Some("thingy"));
+    assert_eq!(filters.text, Some(vec!["a".to_owned(), "b".to_owned()]));
+
+    let limited = |query: &str| Form::<LimitedFilters>::parse_encoded(RawStr::new(query));
+    assert!(limited("num32=1&num32=2&num32=3").is_ok());
+    assert!(limited("text=a&text=b&text=c").is_err());
+    assert!(limited("text=a&text=toolongtext").is_err());
+    assert!(limited("name=toolongname").is_err());
+
+    let normalized =
+        Form::<NormalizedFilters>::parse_encoded(RawStr::new("name=+Thingy+&text=+a+"))
+            .map_err(|err| anyhow!("{err}"))?;
+    assert_eq!(normalized.name.as_deref(), Some("thingy"));
+    assert_eq!(normalized.text, Some(vec!["a".to_owned()]));
+    let too_many = Form::<NormalizedFilters>::parse_encoded(RawStr::new("text=a&text=b&text=c"));
+    assert!(too_many.is_err());
+
+    Ok(())
+}
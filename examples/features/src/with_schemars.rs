@@ -0,0 +1,23 @@
+//! The JSON schema of the filters and of the pagination types.
+
+use crate::ThingyFilters;
+use anyhow::Result;
+use diesel_pagination::{Paginated, PaginationParams};
+use schemars::schema_for;
+use serde_json::json;
+
+pub fn check_schemars() -> Result<()> {
+    let filters = serde_json::to_value(schema_for!(ThingyFilters))?;
+    let properties = &filters["properties"];
+    assert_eq!(properties["num32"]["type"], json!(["integer", "null"]));
+    assert_eq!(properties["text"]["type"], json!(["array", "null"]));
+    assert_eq!(properties["text"]["items"]["type"], json!("string"));
+
+    let pagination = serde_json::to_value(schema_for!(PaginationParams))?;
+    assert_eq!(pagination["properties"]["page"]["minimum"], json!(1.0));
+
+    let page = serde_json::to_value(schema_for!(Paginated<String>))?;
+    assert_eq!(page["properties"]["items"]["type"], json!("array"));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! The query parameters of the filters documented with utoipa.
+
+use crate::{LimitedFilters, ThingyFilters};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use utoipa::{IntoParams, openapi::path::ParameterIn};
+
+/// The parameters of `T` as JSON, by name.
+fn parameters<T: IntoParams>() -> Result<Vec<(String, Value)>> {
+    T::into_params(|| Some(ParameterIn::Query))
+        .into_iter()
+        .map(|parameter| {
+            let name = parameter.name.clone();
+            Ok((name, serde_json::to_value(parameter)?))
+        })
+        .collect()
+}
+
+fn parameter(parameters: &[(String, Value)], name: &str) -> Result<Value> {
+    parameters
+        .iter()
+        .find(|(parameter, _)| parameter == name)
+        .map(|(_, value)| value.clone())
+        .ok_or_else(|| anyhow!("no `{name}` parameter"))
+}
+
+pub fn check_utoipa() -> Result<()> {
+    let thingies = parameters::<ThingyFilters>()?;
+    let text = parameter(&thingies, "text")?;
+    assert_eq!(text["schema"]["items"]["type"], json!("string"));
+
+    let limited = parameters::<LimitedFilters>()?;
+    assert_eq!(parameter(&limited, "text")?["schema"]["maxItems"], json!(2));
+    assert_eq!(
+        parameter(&limited, "name")?["schema"]["maxLength"],
+        json!(8)
+    );
+
+    Ok(())
+}
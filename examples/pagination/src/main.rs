@@ -3,13 +3,16 @@ use diesel::prelude::*;
 use diesel_async::{AsyncConnection, AsyncPgConnection};
 use diesel_filter::DieselFilter;
 use diesel_filter_test_db::{TestDb, custom::CustomType, schema::thingies};
-use diesel_pagination::{Paginate, PaginationParams};
+use diesel_pagination::{Paginate, PaginateAll, PaginationParams};
+use futures_util::TryStreamExt;
 use serde::{Deserialize, Serialize};
+use tokio::task::spawn_blocking;
 use tracing::info;
 use uuid::Uuid;
 
-#[derive(DieselFilter, Queryable, Debug, Serialize, Deserialize)]
+#[derive(DieselFilter, Queryable, Clone, Debug, Serialize, Deserialize)]
 #[diesel(table_name = thingies)]
+#[filter_struct(matches)]
 pub struct Thingy {
     pub id: Uuid,
     #[filter(insensitive)]
@@ -53,7 +56,7 @@ async fn main() -> Result<()> {
             page: Some(1),
             per_page: None,
         })
-        .load_and_count::<Thingy, _>(&mut conn)
+        .load_and_count_async::<Thingy, _>(&mut conn)
         .await?;
     info!(?results_name1);
     assert_eq!(results_name1.items.len(), 1);
@@ -61,7 +64,7 @@ async fn main() -> Result<()> {
 
     let results_all = Thingy::filter(Default::default())
         .paginate(PaginationParams::default())
-        .load_and_count::<Thingy, _>(&mut conn)
+        .load_and_count_async::<Thingy, _>(&mut conn)
         .await?;
     info!(?results_all);
     assert_eq!(results_all.items.len(), 8);
@@ -69,11 +72,143 @@ async fn main() -> Result<()> {
 
     let small_page = Thingy::filter(Default::default())
         .paginate(PaginationParams::page(1).per_page(2))
-        .load_and_count::<Thingy, _>(&mut conn)
+        .load_and_count_async::<Thingy, _>(&mut conn)
         .await?;
     info!(?small_page);
     assert_eq!(small_page.items.len(), 2);
     assert_eq!(small_page.num_total, 8);
 
+    let counted_separately = Thingy::filter(Default::default())
+        .paginate(PaginationParams::page(2).per_page(3))
+        .count_separately_with(Thingy::filter(Default::default()))
+        .load_and_count_async::<Thingy, _>(&mut conn)
+        .await?;
+    info!(?counted_separately);
+    assert_eq!(counted_separately.items.len(), 3);
+    assert_eq!(counted_separately.num_total, 8);
+
+    // Both statements borrow the query with a sync connection
+    let url = db.url().to_owned();
+    let counted_sync = spawn_blocking(move || -> Result<_> {
+        let mut conn = PgConnection::establish(&url)?;
+        let page = Thingy::filter(Default::default())
+            .order(thingies::id)
+            .paginate(PaginationParams::page(3).per_page(3))
+            .count_separately()
+            .load_and_count::<Thingy, _>(&mut conn)?;
+        Ok(page)
+    })
+    .await??;
+    info!(?counted_sync);
+    assert_eq!(counted_sync.items.len(), 2);
+    assert_eq!(counted_sync.num_total, 8);
+
+    // The queries a window function can't count over, counted by a second statement
+    let url = db.url().to_owned();
+    let (joined, distinct, grouped, unordered) = spawn_blocking(move || -> Result<_> {
+        let mut conn = PgConnection::establish(&url)?;
+        let next = diesel::alias!(thingies as next);
+        // Both `id` columns are selected, each thingy with the next one
+        let joined_query = thingies::table
+            .inner_join(next.on(next.field(thingies::num32).eq(thingies::num32 + 1)))
+            .select((thingies::id, next.field(thingies::id)));
+        let joined = joined_query
+            .order(thingies::num32)
+            .paginate(PaginationParams::page(2).per_page(5))
+            .count_separately_with(joined_query)
+            .load_and_count::<(Uuid, Uuid), _>(&mut conn)?;
+        let distinct = thingies::table
+            .distinct_on(thingies::option_num32.is_null())
+            .select((thingies::option_num32.is_null(), thingies::name))
+            .order((thingies::option_num32.is_null(), thingies::name))
+            .paginate(PaginationParams::page(1).per_page(1))
+            .count_separately()
+            .load_and_count::<(bool, String), _>(&mut conn)?;
+        let grouped = thingies::table
+            .group_by(thingies::option_num32)
+            .select((thingies::option_num32, diesel::dsl::count_star()))
+            .paginate(PaginationParams::page(1).per_page(5))
+            .count_separately()
+            .load_and_count::<(Option<i32>, i64), _>(&mut conn)?;
+        let unordered = Thingy::filter(Default::default())
+            .order(thingies::name.desc())
+            .paginate(PaginationParams::page(1).per_page(3))
+            .count_separately_with(Thingy::filter(Default::default()))
+            .load_and_count::<Thingy, _>(&mut conn)?;
+        Ok((joined, distinct, grouped, unordered))
+    })
+    .await??;
+    info!(?joined, ?distinct, ?grouped, ?unordered);
+    assert_eq!(joined.items.len(), 2);
+    assert_eq!(joined.num_total, 7);
+    assert_eq!(distinct.items, [(false, "name1".to_owned())]);
+    assert_eq!(distinct.num_total, 2);
+    // 1, 2, 4, 6, 7, 8 and null
+    assert_eq!(grouped.items.len(), 5);
+    assert_eq!(grouped.num_total, 7);
+    assert_eq!(unordered.items[0].name, "name8");
+    assert_eq!(unordered.num_total, 8);
+
+    let url = db.url().to_owned();
+    let all_pages_sync = spawn_blocking(move || -> Result<_> {
+        let mut conn = PgConnection::establish(&url)?;
+        let pages = (|| Thingy::filter(Default::default()))
+            .paginate_all(thingies::id, 5)
+            .load_iter(&mut conn, |thingy: &Thingy| thingy.id)
+            .collect::<QueryResult<Vec<_>>>()?;
+        Ok(pages)
+    })
+    .await??;
+    info!(?all_pages_sync);
+    assert_eq!(all_pages_sync.len(), 2);
+    assert_eq!(all_pages_sync.iter().map(Vec::len).sum::<usize>(), 8);
+
+    let in_memory_filters = || ThingyFilters {
+        text: Some(vec!["1".to_owned(), "3".to_owned(), "5".to_owned()]),
+        ..Default::default()
+    };
+    let mut cached = results_all.items;
+    cached.sort_by_key(|thingy| thingy.id);
+    // Only the items of the page are cloned from a borrowed `Vec`
+    let all_cached = (&cached)
+        .paginate(PaginationParams::page(1).per_page(3))
+        .into_paginated();
+    assert_eq!(all_cached.items.len(), 3);
+    assert_eq!(all_cached.num_total, 8);
+    let in_memory = cached
+        .into_iter()
+        .filter(|thingy| in_memory_filters().matches(thingy))
+        .collect::<Vec<_>>()
+        .paginate(PaginationParams::page(2).per_page(2))
+        .into_paginated();
+    let in_database = Thingy::filter(in_memory_filters())
+        .order(thingies::id)
+        .paginate(PaginationParams::page(2).per_page(2))
+        .load_and_count_async::<Thingy, _>(&mut conn)
+        .await?;
+    info!(?in_memory);
+    assert_eq!(in_memory.num_total, in_database.num_total);
+    assert_eq!(
+        in_memory
+            .items
+            .iter()
+            .map(|thingy| thingy.id)
+            .collect::<Vec<_>>(),
+        in_database
+            .items
+            .iter()
+            .map(|thingy| thingy.id)
+            .collect::<Vec<_>>(),
+    );
+
+    let all_pages = (|| Thingy::filter(Default::default()))
+        .paginate_all(thingies::id, 3)
+        .load_stream(&mut conn, |thingy: &Thingy| thingy.id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    info!(?all_pages);
+    assert_eq!(all_pages.len(), 3);
+    assert_eq!(all_pages.iter().map(Vec::len).sum::<usize>(), 8);
+
     Ok(())
 }